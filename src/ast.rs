@@ -0,0 +1,701 @@
+// AST produced by the parser. Node shapes follow the ESTree spec,
+// every node carries the `start` and `end` offsets of its source.
+
+use std::borrow::Cow;
+use crate::tokenizer::{Comment, SourceType};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Program<'a> {
+    pub body: Vec<Statement<'a>>,
+    pub source_type: SourceType,
+    // all comments in the source, if `Options::collect_comments` is set
+    pub comments: Vec<Comment<'a>>,
+    pub start: usize,
+    pub end: usize
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Statement<'a> {
+    pub kind: StatementKind<'a>,
+    // comments between the previous token and the statement
+    pub leading_comments: Vec<Comment<'a>>,
+    // comments after the statement, on the line it ends on
+    pub trailing_comments: Vec<Comment<'a>>,
+    pub start: usize,
+    pub end: usize
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum StatementKind<'a> {
+    Expression(Expression<'a>),
+    Block(Block<'a>),
+    Empty,
+    Debugger,
+    With { object: Expression<'a>, body: Box<Statement<'a>> },
+    Return(Option<Expression<'a>>),
+    Labeled { label: Identifier, body: Box<Statement<'a>> },
+    Break(Option<Identifier>),
+    Continue(Option<Identifier>),
+    If { test: Expression<'a>, consequent: Box<Statement<'a>>, alternate: Option<Box<Statement<'a>>> },
+    Switch { discriminant: Expression<'a>, cases: Vec<SwitchCase<'a>> },
+    Throw(Expression<'a>),
+    Try { block: Block<'a>, handler: Option<CatchClause<'a>>, finalizer: Option<Block<'a>> },
+    While { test: Expression<'a>, body: Box<Statement<'a>> },
+    DoWhile { body: Box<Statement<'a>>, test: Expression<'a> },
+    For {
+        init: Option<ForInit<'a>>,
+        test: Option<Expression<'a>>,
+        update: Option<Expression<'a>>,
+        body: Box<Statement<'a>>
+    },
+    ForIn { left: ForInit<'a>, right: Expression<'a>, body: Box<Statement<'a>> },
+    // `is_await` is set on `for await (a of b)`
+    ForOf { left: ForInit<'a>, right: Expression<'a>, body: Box<Statement<'a>>, is_await: bool },
+    FunctionDeclaration(Function<'a>),
+    ClassDeclaration(Class<'a>),
+    VariableDeclaration(VariableDeclaration<'a>),
+    // module items, only at the top level of modules
+    Import { specifiers: Vec<ImportSpecifier>, source: Expression<'a> },
+    ExportNamed {
+        // a variable, function or class declaration, if there are no specifiers
+        declaration: Option<Box<Statement<'a>>>,
+        specifiers: Vec<ExportSpecifier>,
+        source: Option<Expression<'a>>
+    },
+    ExportDefault(ExportDefault<'a>),
+    // `export * from "mod"` or `export * as name from "mod"`
+    ExportAll { exported: Option<Identifier>, source: Expression<'a> }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Block<'a> {
+    pub body: Vec<Statement<'a>>,
+    pub start: usize,
+    pub end: usize
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SwitchCase<'a> {
+    // `None` for the default clause
+    pub test: Option<Expression<'a>>,
+    pub consequent: Vec<Statement<'a>>,
+    pub start: usize,
+    pub end: usize
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CatchClause<'a> {
+    // `None` for `catch {}`
+    pub param: Option<Pattern<'a>>,
+    pub body: Block<'a>,
+    pub start: usize,
+    pub end: usize
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ForInit<'a> {
+    VariableDeclaration(VariableDeclaration<'a>),
+    Expression(Expression<'a>),
+    // the target of a `for`-`in` or `for`-`of` loop
+    Pattern(Pattern<'a>)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct VariableDeclaration<'a> {
+    pub declarations: Vec<VariableDeclarator<'a>>,
+    pub kind: VariableKind,
+    pub start: usize,
+    pub end: usize
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VariableKind {
+    Var,
+    Let,
+    Const
+}
+
+impl VariableKind {
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            VariableKind::Var => "var",
+            VariableKind::Let => "let",
+            VariableKind::Const => "const"
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct VariableDeclarator<'a> {
+    pub id: Pattern<'a>,
+    pub init: Option<Expression<'a>>,
+    pub start: usize,
+    pub end: usize
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportSpecifier {
+    pub kind: ImportSpecifierKind,
+    pub local: Identifier,
+    pub start: usize,
+    pub end: usize
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ImportSpecifierKind {
+    // `import { imported as local } from "mod"`
+    Named(Identifier),
+    // `import local from "mod"`
+    Default,
+    // `import * as local from "mod"`
+    Namespace
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportSpecifier {
+    pub local: Identifier,
+    pub exported: Identifier,
+    pub start: usize,
+    pub end: usize
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExportDefault<'a> {
+    // the function or class may be anonymous
+    Function(Function<'a>),
+    Class(Class<'a>),
+    Expression(Expression<'a>)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Function<'a> {
+    pub id: Option<Identifier>,
+    pub params: Vec<Pattern<'a>>,
+    pub body: Block<'a>,
+    // `function*`
+    pub generator: bool,
+    // `async function`
+    pub is_async: bool,
+    pub start: usize,
+    pub end: usize
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArrowFunction<'a> {
+    pub params: Vec<Pattern<'a>>,
+    pub body: ArrowBody<'a>,
+    // `async (a) => b`
+    pub is_async: bool,
+    pub start: usize,
+    pub end: usize
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ArrowBody<'a> {
+    Block(Block<'a>),
+    // `a => a + 1`, the value is returned
+    Expression(Box<Expression<'a>>)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Class<'a> {
+    pub id: Option<Identifier>,
+    // the expression after `extends`
+    pub super_class: Option<Box<Expression<'a>>>,
+    pub body: ClassBody<'a>,
+    pub start: usize,
+    pub end: usize
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClassBody<'a> {
+    pub body: Vec<ClassMember<'a>>,
+    pub start: usize,
+    pub end: usize
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ClassMember<'a> {
+    Method(MethodDefinition<'a>),
+    // a field, like `a = 1;`
+    Property(PropertyDefinition<'a>),
+    // `static { ... }`, run once when the class is defined
+    StaticBlock(Block<'a>)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MethodDefinition<'a> {
+    // an identifier, private identifier, string or number literal
+    // unless computed
+    pub key: Expression<'a>,
+    pub value: Function<'a>,
+    pub kind: MethodKind,
+    pub computed: bool,
+    pub is_static: bool,
+    pub start: usize,
+    pub end: usize
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MethodKind {
+    Constructor,
+    Method,
+    Get,
+    Set
+}
+
+impl MethodKind {
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            MethodKind::Constructor => "constructor",
+            MethodKind::Method => "method",
+            MethodKind::Get => "get",
+            MethodKind::Set => "set"
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PropertyDefinition<'a> {
+    pub key: Expression<'a>,
+    // `None` for `a;`
+    pub value: Option<Expression<'a>>,
+    pub computed: bool,
+    pub is_static: bool,
+    pub start: usize,
+    pub end: usize
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Identifier {
+    pub name: String,
+    pub start: usize,
+    pub end: usize
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Expression<'a> {
+    pub kind: ExpressionKind<'a>,
+    pub start: usize,
+    pub end: usize
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExpressionKind<'a> {
+    Identifier(String),
+    // `#a`, the name of a class member, a member after `.` or the left
+    // side of `in`
+    PrivateIdentifier(String),
+    Literal(Literal<'a>),
+    This,
+    // `None` elements are holes, as in `[a, , b]`
+    Array(Vec<Option<Expression<'a>>>),
+    Object(Vec<ObjectMember<'a>>),
+    Function(Function<'a>),
+    ArrowFunction(ArrowFunction<'a>),
+    Class(Class<'a>),
+    // only as a callee or object, as in `super(a)` and `super.a`
+    Super,
+    Unary { operator: UnaryOperator, argument: Box<Expression<'a>> },
+    Update { operator: UpdateOperator, prefix: bool, argument: Box<Expression<'a>> },
+    Binary { operator: BinaryOperator, left: Box<Expression<'a>>, right: Box<Expression<'a>> },
+    Logical { operator: LogicalOperator, left: Box<Expression<'a>>, right: Box<Expression<'a>> },
+    Assignment { operator: AssignmentOperator, left: Box<Pattern<'a>>, right: Box<Expression<'a>> },
+    Conditional { test: Box<Expression<'a>>, consequent: Box<Expression<'a>>, alternate: Box<Expression<'a>> },
+    // `optional` is set for `a?.()`
+    Call { callee: Box<Expression<'a>>, arguments: Vec<Expression<'a>>, optional: bool },
+    New { callee: Box<Expression<'a>>, arguments: Vec<Expression<'a>> },
+    // `optional` is set for `a?.b` and `a?.[b]`
+    Member { object: Box<Expression<'a>>, property: Box<Expression<'a>>, computed: bool, optional: bool },
+    // an optional chain like `a?.b.c()`, wrapping its outermost member or call
+    Chain(Box<Expression<'a>>),
+    Sequence(Vec<Expression<'a>>),
+    // `delegate` is set for `yield*`
+    Yield { argument: Option<Box<Expression<'a>>>, delegate: bool },
+    Await(Box<Expression<'a>>),
+    // `new.target` or `import.meta`
+    MetaProperty { meta: Identifier, property: Identifier },
+    Template(TemplateLiteral<'a>),
+    // `...a`, only as an element of an array, an argument or an object member
+    Spread(Box<Expression<'a>>),
+    // a template called as a function, like `tag`a${b}``
+    TaggedTemplate { tag: Box<Expression<'a>>, quasi: TemplateLiteral<'a> }
+}
+
+// The chunks of a template literal surround its substitutions, there
+// is always one more chunk than there are expressions
+#[derive(Debug, Clone, PartialEq)]
+pub struct TemplateLiteral<'a> {
+    pub quasis: Vec<TemplateElement>,
+    pub expressions: Vec<Expression<'a>>,
+    pub start: usize,
+    pub end: usize
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TemplateElement {
+    // the value with escapes interpreted
+    pub cooked: String,
+    // the source text, with line endings normalized to '\n'
+    pub raw: String,
+    // set on the last chunk
+    pub tail: bool,
+    pub start: usize,
+    pub end: usize
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Literal<'a> {
+    Null,
+    Boolean(bool),
+    Number(f64),
+    // decimal digits
+    BigInt(Cow<'a, str>),
+    String(String),
+    Regexp { pattern: String, flags: String }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ObjectMember<'a> {
+    Property(Property<'a>),
+    // an expression of kind `Spread`
+    Spread(Expression<'a>)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Property<'a> {
+    // an identifier, string or number literal unless computed
+    pub key: Expression<'a>,
+    pub value: Expression<'a>,
+    pub kind: PropertyKind,
+    // `[a]: b`, the key is any expression
+    pub computed: bool,
+    // `{a() {}}`, the value is a function
+    pub method: bool,
+    // `{a}`, the value is the key
+    pub shorthand: bool,
+    pub start: usize,
+    pub end: usize
+}
+
+// The target of a declaration, a parameter or an assignment
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pattern<'a> {
+    pub kind: PatternKind<'a>,
+    pub start: usize,
+    pub end: usize
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PatternKind<'a> {
+    Identifier(String),
+    // `None` elements are holes, as in `[a, , b]`
+    Array(Vec<Option<Pattern<'a>>>),
+    Object(Vec<ObjectPatternMember<'a>>),
+    // `...a`, last in an array or object pattern or a parameter list
+    Rest(Box<Pattern<'a>>),
+    // `a = 1`, a target with a default value
+    Assignment { left: Box<Pattern<'a>>, right: Box<Expression<'a>> },
+    // only in assignments, as in `[a.b] = c`
+    Member(Box<Expression<'a>>)
+}
+
+impl<'a> Pattern<'a> {
+    // The names a declaration binds, with their start offsets
+    pub fn bound_names(&self) -> Vec<(&str, usize)> {
+        let mut names = Vec::new();
+        self.collect_bound_names(&mut names);
+        names
+    }
+
+    fn collect_bound_names<'p>(&'p self, names: &mut Vec<(&'p str, usize)>) {
+        match self.kind {
+            PatternKind::Identifier(ref name) => names.push((name, self.start)),
+            PatternKind::Array(ref elements) => {
+                for element in elements.iter().flatten() {
+                    element.collect_bound_names(names);
+                }
+            },
+            PatternKind::Object(ref members) => {
+                for member in members {
+                    match *member {
+                        ObjectPatternMember::Property(ref property) => property.value.collect_bound_names(names),
+                        ObjectPatternMember::Rest(ref rest) => rest.collect_bound_names(names)
+                    }
+                }
+            },
+            PatternKind::Rest(ref argument) => argument.collect_bound_names(names),
+            PatternKind::Assignment { ref left, .. } => left.collect_bound_names(names),
+            PatternKind::Member(_) => {}
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ObjectPatternMember<'a> {
+    Property(PatternProperty<'a>),
+    // a pattern of kind `Rest`
+    Rest(Pattern<'a>)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PatternProperty<'a> {
+    pub key: Expression<'a>,
+    pub value: Pattern<'a>,
+    pub computed: bool,
+    pub shorthand: bool,
+    pub start: usize,
+    pub end: usize
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PropertyKind {
+    Init,
+    Get,
+    Set
+}
+
+impl PropertyKind {
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            PropertyKind::Init => "init",
+            PropertyKind::Get => "get",
+            PropertyKind::Set => "set"
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UnaryOperator {
+    Minus,
+    Plus,
+    Not,
+    BitwiseNot,
+    Typeof,
+    Void,
+    Delete
+}
+
+impl UnaryOperator {
+    pub fn from_op(op: &str) -> Option<UnaryOperator> {
+        match op {
+            "-" => Some(UnaryOperator::Minus),
+            "+" => Some(UnaryOperator::Plus),
+            "!" => Some(UnaryOperator::Not),
+            "~" => Some(UnaryOperator::BitwiseNot),
+            "typeof" => Some(UnaryOperator::Typeof),
+            "void" => Some(UnaryOperator::Void),
+            "delete" => Some(UnaryOperator::Delete),
+            _ => None
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            UnaryOperator::Minus => "-",
+            UnaryOperator::Plus => "+",
+            UnaryOperator::Not => "!",
+            UnaryOperator::BitwiseNot => "~",
+            UnaryOperator::Typeof => "typeof",
+            UnaryOperator::Void => "void",
+            UnaryOperator::Delete => "delete"
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UpdateOperator {
+    Increment,
+    Decrement
+}
+
+impl UpdateOperator {
+    pub fn from_op(op: &str) -> Option<UpdateOperator> {
+        match op {
+            "++" => Some(UpdateOperator::Increment),
+            "--" => Some(UpdateOperator::Decrement),
+            _ => None
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            UpdateOperator::Increment => "++",
+            UpdateOperator::Decrement => "--"
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BinaryOperator {
+    Equal,
+    NotEqual,
+    StrictEqual,
+    StrictNotEqual,
+    LessThan,
+    LessThanEqual,
+    GreaterThan,
+    GreaterThanEqual,
+    LeftShift,
+    RightShift,
+    UnsignedRightShift,
+    Plus,
+    Minus,
+    Multiply,
+    Divide,
+    Modulo,
+    Exponent,
+    BitwiseOr,
+    BitwiseXor,
+    BitwiseAnd,
+    In,
+    Instanceof
+}
+
+impl BinaryOperator {
+    pub fn from_op(op: &str) -> Option<BinaryOperator> {
+        match op {
+            "==" => Some(BinaryOperator::Equal),
+            "!=" => Some(BinaryOperator::NotEqual),
+            "===" => Some(BinaryOperator::StrictEqual),
+            "!==" => Some(BinaryOperator::StrictNotEqual),
+            "<" => Some(BinaryOperator::LessThan),
+            "<=" => Some(BinaryOperator::LessThanEqual),
+            ">" => Some(BinaryOperator::GreaterThan),
+            ">=" => Some(BinaryOperator::GreaterThanEqual),
+            "<<" => Some(BinaryOperator::LeftShift),
+            ">>" => Some(BinaryOperator::RightShift),
+            ">>>" => Some(BinaryOperator::UnsignedRightShift),
+            "+" => Some(BinaryOperator::Plus),
+            "-" => Some(BinaryOperator::Minus),
+            "*" => Some(BinaryOperator::Multiply),
+            "/" => Some(BinaryOperator::Divide),
+            "%" => Some(BinaryOperator::Modulo),
+            "**" => Some(BinaryOperator::Exponent),
+            "|" => Some(BinaryOperator::BitwiseOr),
+            "^" => Some(BinaryOperator::BitwiseXor),
+            "&" => Some(BinaryOperator::BitwiseAnd),
+            "in" => Some(BinaryOperator::In),
+            "instanceof" => Some(BinaryOperator::Instanceof),
+            _ => None
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            BinaryOperator::Equal => "==",
+            BinaryOperator::NotEqual => "!=",
+            BinaryOperator::StrictEqual => "===",
+            BinaryOperator::StrictNotEqual => "!==",
+            BinaryOperator::LessThan => "<",
+            BinaryOperator::LessThanEqual => "<=",
+            BinaryOperator::GreaterThan => ">",
+            BinaryOperator::GreaterThanEqual => ">=",
+            BinaryOperator::LeftShift => "<<",
+            BinaryOperator::RightShift => ">>",
+            BinaryOperator::UnsignedRightShift => ">>>",
+            BinaryOperator::Plus => "+",
+            BinaryOperator::Minus => "-",
+            BinaryOperator::Multiply => "*",
+            BinaryOperator::Divide => "/",
+            BinaryOperator::Modulo => "%",
+            BinaryOperator::Exponent => "**",
+            BinaryOperator::BitwiseOr => "|",
+            BinaryOperator::BitwiseXor => "^",
+            BinaryOperator::BitwiseAnd => "&",
+            BinaryOperator::In => "in",
+            BinaryOperator::Instanceof => "instanceof"
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LogicalOperator {
+    Or,
+    And,
+    NullishCoalescing
+}
+
+impl LogicalOperator {
+    pub fn from_op(op: &str) -> Option<LogicalOperator> {
+        match op {
+            "||" => Some(LogicalOperator::Or),
+            "&&" => Some(LogicalOperator::And),
+            "??" => Some(LogicalOperator::NullishCoalescing),
+            _ => None
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            LogicalOperator::Or => "||",
+            LogicalOperator::And => "&&",
+            LogicalOperator::NullishCoalescing => "??"
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AssignmentOperator {
+    Assign,
+    PlusAssign,
+    MinusAssign,
+    MultiplyAssign,
+    DivideAssign,
+    ModuloAssign,
+    ExponentAssign,
+    LeftShiftAssign,
+    RightShiftAssign,
+    UnsignedRightShiftAssign,
+    BitwiseOrAssign,
+    BitwiseXorAssign,
+    BitwiseAndAssign,
+    OrAssign,
+    AndAssign,
+    NullishAssign
+}
+
+impl AssignmentOperator {
+    pub fn from_op(op: &str) -> Option<AssignmentOperator> {
+        match op {
+            "=" => Some(AssignmentOperator::Assign),
+            "+=" => Some(AssignmentOperator::PlusAssign),
+            "-=" => Some(AssignmentOperator::MinusAssign),
+            "*=" => Some(AssignmentOperator::MultiplyAssign),
+            "/=" => Some(AssignmentOperator::DivideAssign),
+            "%=" => Some(AssignmentOperator::ModuloAssign),
+            "**=" => Some(AssignmentOperator::ExponentAssign),
+            "<<=" => Some(AssignmentOperator::LeftShiftAssign),
+            ">>=" => Some(AssignmentOperator::RightShiftAssign),
+            ">>>=" => Some(AssignmentOperator::UnsignedRightShiftAssign),
+            "|=" => Some(AssignmentOperator::BitwiseOrAssign),
+            "^=" => Some(AssignmentOperator::BitwiseXorAssign),
+            "&=" => Some(AssignmentOperator::BitwiseAndAssign),
+            "||=" => Some(AssignmentOperator::OrAssign),
+            "&&=" => Some(AssignmentOperator::AndAssign),
+            "??=" => Some(AssignmentOperator::NullishAssign),
+            _ => None
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            AssignmentOperator::Assign => "=",
+            AssignmentOperator::PlusAssign => "+=",
+            AssignmentOperator::MinusAssign => "-=",
+            AssignmentOperator::MultiplyAssign => "*=",
+            AssignmentOperator::DivideAssign => "/=",
+            AssignmentOperator::ModuloAssign => "%=",
+            AssignmentOperator::ExponentAssign => "**=",
+            AssignmentOperator::LeftShiftAssign => "<<=",
+            AssignmentOperator::RightShiftAssign => ">>=",
+            AssignmentOperator::UnsignedRightShiftAssign => ">>>=",
+            AssignmentOperator::BitwiseOrAssign => "|=",
+            AssignmentOperator::BitwiseXorAssign => "^=",
+            AssignmentOperator::BitwiseAndAssign => "&=",
+            AssignmentOperator::OrAssign => "||=",
+            AssignmentOperator::AndAssign => "&&=",
+            AssignmentOperator::NullishAssign => "??="
+        }
+    }
+}
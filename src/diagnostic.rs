@@ -0,0 +1,62 @@
+// Renders parse errors for humans, with the offending source line and
+// the error span underlined:
+//
+// error: Unexpected token
+//  --> app.js:2:5
+//   |
+// 2 | var a b;
+//   |       ^
+
+use crate::location::LineIndex;
+use crate::tokenizer::ParseError;
+
+const RED: &str = "\x1b[1;31m";
+const BLUE: &str = "\x1b[1;34m";
+const BOLD: &str = "\x1b[1m";
+const RESET: &str = "\x1b[0m";
+
+/// Renders `error` found in `source` read from `file_name`,
+/// `color` turns on ANSI escape codes.
+pub fn render_error(error: &ParseError, file_name: &str, source: &str, color: bool) -> String {
+    let paint = |style: &str, text: &str| {
+        if color { format!("{}{}{}", style, text, RESET) } else { text.to_string() }
+    };
+    let index = LineIndex::new(source);
+    let pos = error.pos.min(source.len());
+    let start = index.position(pos);
+    let line_start = index.line_start(start.line);
+    let line_text = source[line_start..].split(['\n', '\r', '\u{2028}', '\u{2029}']).next().unwrap_or("");
+
+    // the span may only be underlined on the line it starts on
+    let end_column = if error.loc.end.line == start.line && error.loc.end.column > start.column {
+        error.loc.end.column
+    } else {
+        start.column + 1
+    };
+    let mut padding = String::new();
+    let mut underline_len = 0;
+    let mut column = 0;
+    for ch in line_text.chars() {
+        if column < start.column {
+            // keep tabs so the underline lines up with the source
+            padding.push(if ch == '\t' { '\t' } else { ' ' });
+        } else if column < end_column {
+            underline_len += 1;
+        } else {
+            break;
+        }
+        column += ch.len_utf16();
+    }
+    // errors at the end of a line or input still get a caret
+    let underline = "^".repeat(underline_len.max(1));
+
+    let line_number = start.line.to_string();
+    let gutter = " ".repeat(line_number.len());
+    let mut out = String::new();
+    out.push_str(&format!("{}{}\n", paint(RED, "error"), paint(BOLD, &format!(": {}", error.kind))));
+    out.push_str(&format!("{}{} {}:{}:{}\n", gutter, paint(BLUE, "-->"), file_name, start.line, start.column + 1));
+    out.push_str(&format!("{} {}\n", gutter, paint(BLUE, "|")));
+    out.push_str(&format!("{} {} {}\n", paint(BLUE, &line_number), paint(BLUE, "|"), line_text));
+    out.push_str(&format!("{} {} {}{}\n", gutter, paint(BLUE, "|"), padding, paint(RED, &underline)));
+    out
+}
@@ -0,0 +1,630 @@
+// Serializes the AST to ESTree JSON, the format produced by esprima and acorn.
+// Offsets in `start`, `end`, `range` and `loc` columns are counted in UTF-16
+// code units, like in JavaScript, so the output can be compared to acorn's.
+
+use crate::ast::*;
+use crate::location::LineIndex;
+use crate::tokenizer::{Comment, CommentKind, SourceType};
+
+/// Serializes a program parsed from `source` to ESTree JSON
+pub fn to_estree_json(program: &Program, source: &str) -> String {
+    let serializer = Serializer { source, index: LineIndex::new(source) };
+    let mut out = String::new();
+    serializer.program(program).write(&mut out);
+    out
+}
+
+enum Json {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(&'static str, Json)>)
+}
+
+impl Json {
+    fn str(value: &str) -> Json {
+        Json::String(value.to_string())
+    }
+
+    fn write(&self, out: &mut String) {
+        match *self {
+            Json::Null => out.push_str("null"),
+            Json::Bool(value) => out.push_str(if value { "true" } else { "false" }),
+            // JSON has no NaN or Infinity
+            Json::Number(value) if !value.is_finite() => out.push_str("null"),
+            Json::Number(value) => write_json_number(value, out),
+            Json::String(ref value) => write_json_string(value, out),
+            Json::Array(ref items) => {
+                out.push('[');
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 { out.push(','); }
+                    item.write(out);
+                }
+                out.push(']');
+            },
+            Json::Object(ref fields) => {
+                out.push('{');
+                for (i, &(key, ref value)) in fields.iter().enumerate() {
+                    if i > 0 { out.push(','); }
+                    write_json_string(key, out);
+                    out.push(':');
+                    value.write(out);
+                }
+                out.push('}');
+            }
+        }
+    }
+}
+
+// Formats like JS `Number#toString`, which uses the exponent form
+// outside [1e-6, 1e21) and writes -0 as 0. Both print the shortest
+// digits that round trip.
+fn write_json_number(value: f64, out: &mut String) {
+    if value == 0.0 {
+        out.push('0');
+    } else if (1e-6..1e21).contains(&value.abs()) {
+        out.push_str(&value.to_string());
+    } else {
+        let formatted = format!("{:e}", value);
+        if formatted.contains("e-") {
+            out.push_str(&formatted);
+        } else {
+            out.push_str(&formatted.replacen('e', "e+", 1));
+        }
+    }
+}
+
+fn write_json_string(value: &str, out: &mut String) {
+    out.push('"');
+    for ch in value.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\u{8}' => out.push_str("\\b"),
+            '\u{c}' => out.push_str("\\f"),
+            // line and paragraph separators are not valid in JavaScript strings
+            '\u{0}'..='\u{1f}' | '\u{2028}' | '\u{2029}' => out.push_str(&format!("\\u{:04x}", ch as u32)),
+            _ => out.push(ch)
+        }
+    }
+    out.push('"');
+}
+
+struct Serializer<'a> {
+    source: &'a str,
+    index: LineIndex
+}
+
+impl<'a> Serializer<'a> {
+    // Creates a node object with the type and location fields every node has
+    fn node(&self, node_type: &str, start: usize, end: usize, mut fields: Vec<(&'static str, Json)>) -> Json {
+        let start16 = self.index.utf16_offset(start);
+        let end16 = self.index.utf16_offset(end);
+        let mut node = vec![
+            ("type", Json::str(node_type)),
+            ("start", Json::Number(start16 as f64)),
+            ("end", Json::Number(end16 as f64)),
+            ("loc", Json::Object(vec![
+                ("start", self.position(start)),
+                ("end", self.position(end))
+            ])),
+            ("range", Json::Array(vec![Json::Number(start16 as f64), Json::Number(end16 as f64)]))
+        ];
+        node.append(&mut fields);
+        Json::Object(node)
+    }
+
+    fn position(&self, pos: usize) -> Json {
+        let position = self.index.position(pos);
+        Json::Object(vec![
+            ("line", Json::Number(position.line as f64)),
+            ("column", Json::Number(position.column as f64))
+        ])
+    }
+
+    fn program(&self, program: &Program) -> Json {
+        let mut fields = vec![
+            ("body", self.statements(&program.body)),
+            ("sourceType", Json::str(match program.source_type {
+                SourceType::Script => "script",
+                SourceType::Module => "module"
+            }))
+        ];
+        if !program.comments.is_empty() {
+            fields.push(("comments", self.comments(&program.comments)));
+        }
+        self.node("Program", program.start, program.end, fields)
+    }
+
+    // Comments are serialized the way esprima does
+    fn comments(&self, comments: &[Comment]) -> Json {
+        Json::Array(comments.iter().map(|comment| {
+            let comment_type = match comment.kind {
+                CommentKind::Line | CommentKind::Html => "Line",
+                CommentKind::Block => "Block",
+                CommentKind::Hashbang => "Hashbang"
+            };
+            self.node(comment_type, comment.start, comment.end, vec![("value", Json::str(comment.text))])
+        }).collect())
+    }
+
+    fn statements(&self, statements: &[Statement]) -> Json {
+        Json::Array(statements.iter().map(|s| self.statement(s)).collect())
+    }
+
+    fn boxed_statement(&self, statement: &Option<Box<Statement>>) -> Json {
+        match *statement {
+            Some(ref statement) => self.statement(statement),
+            None => Json::Null
+        }
+    }
+
+    fn statement(&self, statement: &Statement) -> Json {
+        let mut json = self.statement_node(statement);
+        if let Json::Object(ref mut fields) = json {
+            if !statement.leading_comments.is_empty() {
+                fields.push(("leadingComments", self.comments(&statement.leading_comments)));
+            }
+            if !statement.trailing_comments.is_empty() {
+                fields.push(("trailingComments", self.comments(&statement.trailing_comments)));
+            }
+        }
+        json
+    }
+
+    fn statement_node(&self, statement: &Statement) -> Json {
+        let (node_type, fields) = match statement.kind {
+            StatementKind::Expression(ref expression) => ("ExpressionStatement", vec![
+                ("expression", self.expression(expression))
+            ]),
+            StatementKind::Block(ref block) => return self.block(block),
+            StatementKind::Empty => ("EmptyStatement", vec![]),
+            StatementKind::Debugger => ("DebuggerStatement", vec![]),
+            StatementKind::With { ref object, ref body } => ("WithStatement", vec![
+                ("object", self.expression(object)),
+                ("body", self.statement(body))
+            ]),
+            StatementKind::Return(ref argument) => ("ReturnStatement", vec![
+                ("argument", self.optional_expression(argument))
+            ]),
+            StatementKind::Labeled { ref label, ref body } => ("LabeledStatement", vec![
+                ("label", self.identifier(label)),
+                ("body", self.statement(body))
+            ]),
+            StatementKind::Break(ref label) => ("BreakStatement", vec![
+                ("label", self.optional_identifier(label))
+            ]),
+            StatementKind::Continue(ref label) => ("ContinueStatement", vec![
+                ("label", self.optional_identifier(label))
+            ]),
+            StatementKind::If { ref test, ref consequent, ref alternate } => ("IfStatement", vec![
+                ("test", self.expression(test)),
+                ("consequent", self.statement(consequent)),
+                ("alternate", self.boxed_statement(alternate))
+            ]),
+            StatementKind::Switch { ref discriminant, ref cases } => ("SwitchStatement", vec![
+                ("discriminant", self.expression(discriminant)),
+                ("cases", Json::Array(cases.iter().map(|c| self.switch_case(c)).collect()))
+            ]),
+            StatementKind::Throw(ref argument) => ("ThrowStatement", vec![
+                ("argument", self.expression(argument))
+            ]),
+            StatementKind::Try { ref block, ref handler, ref finalizer } => ("TryStatement", vec![
+                ("block", self.block(block)),
+                ("handler", match *handler {
+                    Some(ref handler) => self.catch_clause(handler),
+                    None => Json::Null
+                }),
+                ("finalizer", match *finalizer {
+                    Some(ref finalizer) => self.block(finalizer),
+                    None => Json::Null
+                })
+            ]),
+            StatementKind::While { ref test, ref body } => ("WhileStatement", vec![
+                ("test", self.expression(test)),
+                ("body", self.statement(body))
+            ]),
+            StatementKind::DoWhile { ref body, ref test } => ("DoWhileStatement", vec![
+                ("body", self.statement(body)),
+                ("test", self.expression(test))
+            ]),
+            StatementKind::For { ref init, ref test, ref update, ref body } => ("ForStatement", vec![
+                ("init", match *init {
+                    Some(ref init) => self.for_init(init),
+                    None => Json::Null
+                }),
+                ("test", self.optional_expression(test)),
+                ("update", self.optional_expression(update)),
+                ("body", self.statement(body))
+            ]),
+            StatementKind::ForIn { ref left, ref right, ref body } => ("ForInStatement", vec![
+                ("left", self.for_init(left)),
+                ("right", self.expression(right)),
+                ("body", self.statement(body))
+            ]),
+            StatementKind::ForOf { ref left, ref right, ref body, is_await } => ("ForOfStatement", vec![
+                ("await", Json::Bool(is_await)),
+                ("left", self.for_init(left)),
+                ("right", self.expression(right)),
+                ("body", self.statement(body))
+            ]),
+            StatementKind::FunctionDeclaration(ref function) => {
+                return self.function("FunctionDeclaration", function)
+            },
+            StatementKind::ClassDeclaration(ref class) => return self.class("ClassDeclaration", class),
+            StatementKind::VariableDeclaration(ref declaration) => {
+                return self.variable_declaration(declaration)
+            },
+            StatementKind::Import { ref specifiers, ref source } => ("ImportDeclaration", vec![
+                ("specifiers", Json::Array(specifiers.iter().map(|s| self.import_specifier(s)).collect())),
+                ("source", self.expression(source))
+            ]),
+            StatementKind::ExportNamed { ref declaration, ref specifiers, ref source } => ("ExportNamedDeclaration", vec![
+                ("declaration", self.boxed_statement(declaration)),
+                ("specifiers", Json::Array(specifiers.iter().map(|s| self.export_specifier(s)).collect())),
+                ("source", self.optional_expression(source))
+            ]),
+            StatementKind::ExportDefault(ref declaration) => ("ExportDefaultDeclaration", vec![
+                ("declaration", match *declaration {
+                    ExportDefault::Function(ref function) => self.function("FunctionDeclaration", function),
+                    ExportDefault::Class(ref class) => self.class("ClassDeclaration", class),
+                    ExportDefault::Expression(ref expression) => self.expression(expression)
+                })
+            ]),
+            StatementKind::ExportAll { ref exported, ref source } => ("ExportAllDeclaration", vec![
+                ("exported", self.optional_identifier(exported)),
+                ("source", self.expression(source))
+            ])
+        };
+        self.node(node_type, statement.start, statement.end, fields)
+    }
+
+    fn import_specifier(&self, specifier: &ImportSpecifier) -> Json {
+        let (node_type, mut fields) = match specifier.kind {
+            ImportSpecifierKind::Named(ref imported) => ("ImportSpecifier", vec![("imported", self.identifier(imported))]),
+            ImportSpecifierKind::Default => ("ImportDefaultSpecifier", vec![]),
+            ImportSpecifierKind::Namespace => ("ImportNamespaceSpecifier", vec![])
+        };
+        fields.push(("local", self.identifier(&specifier.local)));
+        self.node(node_type, specifier.start, specifier.end, fields)
+    }
+
+    fn export_specifier(&self, specifier: &ExportSpecifier) -> Json {
+        self.node("ExportSpecifier", specifier.start, specifier.end, vec![
+            ("local", self.identifier(&specifier.local)),
+            ("exported", self.identifier(&specifier.exported))
+        ])
+    }
+
+    fn block(&self, block: &Block) -> Json {
+        self.node("BlockStatement", block.start, block.end, vec![
+            ("body", self.statements(&block.body))
+        ])
+    }
+
+    fn switch_case(&self, case: &SwitchCase) -> Json {
+        self.node("SwitchCase", case.start, case.end, vec![
+            ("test", self.optional_expression(&case.test)),
+            ("consequent", self.statements(&case.consequent))
+        ])
+    }
+
+    fn catch_clause(&self, clause: &CatchClause) -> Json {
+        self.node("CatchClause", clause.start, clause.end, vec![
+            ("param", match clause.param {
+                Some(ref param) => self.pattern(param),
+                None => Json::Null
+            }),
+            ("body", self.block(&clause.body))
+        ])
+    }
+
+    fn for_init(&self, init: &ForInit) -> Json {
+        match *init {
+            ForInit::VariableDeclaration(ref declaration) => self.variable_declaration(declaration),
+            ForInit::Expression(ref expression) => self.expression(expression),
+            ForInit::Pattern(ref pattern) => self.pattern(pattern)
+        }
+    }
+
+    fn variable_declaration(&self, declaration: &VariableDeclaration) -> Json {
+        let declarations = declaration.declarations.iter().map(|d| {
+            self.node("VariableDeclarator", d.start, d.end, vec![
+                ("id", self.pattern(&d.id)),
+                ("init", self.optional_expression(&d.init))
+            ])
+        }).collect();
+        self.node("VariableDeclaration", declaration.start, declaration.end, vec![
+            ("declarations", Json::Array(declarations)),
+            ("kind", Json::str(declaration.kind.as_str()))
+        ])
+    }
+
+    fn function(&self, node_type: &str, function: &Function) -> Json {
+        self.node(node_type, function.start, function.end, vec![
+            ("id", self.optional_identifier(&function.id)),
+            ("expression", Json::Bool(false)),
+            ("generator", Json::Bool(function.generator)),
+            ("async", Json::Bool(function.is_async)),
+            ("params", Json::Array(function.params.iter().map(|p| self.pattern(p)).collect())),
+            ("body", self.block(&function.body))
+        ])
+    }
+
+    fn arrow_function(&self, arrow: &ArrowFunction) -> Json {
+        let (expression, body) = match arrow.body {
+            ArrowBody::Block(ref block) => (false, self.block(block)),
+            ArrowBody::Expression(ref expression) => (true, self.expression(expression))
+        };
+        self.node("ArrowFunctionExpression", arrow.start, arrow.end, vec![
+            ("id", Json::Null),
+            ("expression", Json::Bool(expression)),
+            ("generator", Json::Bool(false)),
+            ("async", Json::Bool(arrow.is_async)),
+            ("params", Json::Array(arrow.params.iter().map(|p| self.pattern(p)).collect())),
+            ("body", body)
+        ])
+    }
+
+    fn class(&self, node_type: &str, class: &Class) -> Json {
+        let members = class.body.body.iter().map(|member| match *member {
+            ClassMember::Method(ref method) => self.node("MethodDefinition", method.start, method.end, vec![
+                ("static", Json::Bool(method.is_static)),
+                ("computed", Json::Bool(method.computed)),
+                ("key", self.expression(&method.key)),
+                ("kind", Json::str(method.kind.as_str())),
+                ("value", self.function("FunctionExpression", &method.value))
+            ]),
+            ClassMember::Property(ref property) => self.node("PropertyDefinition", property.start, property.end, vec![
+                ("static", Json::Bool(property.is_static)),
+                ("computed", Json::Bool(property.computed)),
+                ("key", self.expression(&property.key)),
+                ("value", self.optional_expression(&property.value))
+            ]),
+            ClassMember::StaticBlock(ref block) => self.node("StaticBlock", block.start, block.end, vec![
+                ("body", self.statements(&block.body))
+            ])
+        }).collect();
+        self.node(node_type, class.start, class.end, vec![
+            ("id", self.optional_identifier(&class.id)),
+            ("superClass", match class.super_class {
+                Some(ref super_class) => self.expression(super_class),
+                None => Json::Null
+            }),
+            ("body", self.node("ClassBody", class.body.start, class.body.end, vec![
+                ("body", Json::Array(members))
+            ]))
+        ])
+    }
+
+    fn pattern(&self, pattern: &Pattern) -> Json {
+        let (node_type, fields) = match pattern.kind {
+            PatternKind::Identifier(ref name) => ("Identifier", vec![
+                ("name", Json::str(name))
+            ]),
+            PatternKind::Array(ref elements) => ("ArrayPattern", vec![
+                ("elements", Json::Array(elements.iter().map(|element| match *element {
+                    Some(ref element) => self.pattern(element),
+                    None => Json::Null
+                }).collect()))
+            ]),
+            PatternKind::Object(ref members) => ("ObjectPattern", vec![
+                ("properties", Json::Array(members.iter().map(|member| match *member {
+                    ObjectPatternMember::Property(ref property) => self.pattern_property(property),
+                    ObjectPatternMember::Rest(ref rest) => self.pattern(rest)
+                }).collect()))
+            ]),
+            PatternKind::Rest(ref argument) => ("RestElement", vec![
+                ("argument", self.pattern(argument))
+            ]),
+            PatternKind::Assignment { ref left, ref right } => ("AssignmentPattern", vec![
+                ("left", self.pattern(left)),
+                ("right", self.expression(right))
+            ]),
+            PatternKind::Member(ref expression) => return self.expression(expression)
+        };
+        self.node(node_type, pattern.start, pattern.end, fields)
+    }
+
+    fn identifier(&self, identifier: &Identifier) -> Json {
+        self.node("Identifier", identifier.start, identifier.end, vec![
+            ("name", Json::str(&identifier.name))
+        ])
+    }
+
+    fn optional_identifier(&self, identifier: &Option<Identifier>) -> Json {
+        match *identifier {
+            Some(ref identifier) => self.identifier(identifier),
+            None => Json::Null
+        }
+    }
+
+    fn optional_expression(&self, expression: &Option<Expression>) -> Json {
+        match *expression {
+            Some(ref expression) => self.expression(expression),
+            None => Json::Null
+        }
+    }
+
+    fn expressions(&self, expressions: &[Expression]) -> Json {
+        Json::Array(expressions.iter().map(|e| self.expression(e)).collect())
+    }
+
+    fn expression(&self, expression: &Expression) -> Json {
+        let (node_type, fields) = match expression.kind {
+            ExpressionKind::Identifier(ref name) => ("Identifier", vec![
+                ("name", Json::str(name))
+            ]),
+            ExpressionKind::PrivateIdentifier(ref name) => ("PrivateIdentifier", vec![
+                ("name", Json::str(name))
+            ]),
+            ExpressionKind::Literal(ref literal) => return self.literal(literal, expression),
+            ExpressionKind::This => ("ThisExpression", vec![]),
+            ExpressionKind::Array(ref elements) => ("ArrayExpression", vec![
+                ("elements", Json::Array(elements.iter().map(|e| self.optional_expression(e)).collect()))
+            ]),
+            ExpressionKind::Object(ref properties) => ("ObjectExpression", vec![
+                ("properties", Json::Array(properties.iter().map(|member| match *member {
+                    ObjectMember::Property(ref property) => self.property(property),
+                    ObjectMember::Spread(ref spread) => self.expression(spread)
+                }).collect()))
+            ]),
+            ExpressionKind::Function(ref function) => {
+                return self.function("FunctionExpression", function)
+            },
+            ExpressionKind::ArrowFunction(ref arrow) => return self.arrow_function(arrow),
+            ExpressionKind::Class(ref class) => return self.class("ClassExpression", class),
+            ExpressionKind::Super => ("Super", vec![]),
+            ExpressionKind::Unary { operator, ref argument } => ("UnaryExpression", vec![
+                ("operator", Json::str(operator.as_str())),
+                ("prefix", Json::Bool(true)),
+                ("argument", self.expression(argument))
+            ]),
+            ExpressionKind::Update { operator, prefix, ref argument } => ("UpdateExpression", vec![
+                ("operator", Json::str(operator.as_str())),
+                ("prefix", Json::Bool(prefix)),
+                ("argument", self.expression(argument))
+            ]),
+            ExpressionKind::Binary { operator, ref left, ref right } => ("BinaryExpression", vec![
+                ("left", self.expression(left)),
+                ("operator", Json::str(operator.as_str())),
+                ("right", self.expression(right))
+            ]),
+            ExpressionKind::Logical { operator, ref left, ref right } => ("LogicalExpression", vec![
+                ("left", self.expression(left)),
+                ("operator", Json::str(operator.as_str())),
+                ("right", self.expression(right))
+            ]),
+            ExpressionKind::Assignment { operator, ref left, ref right } => ("AssignmentExpression", vec![
+                ("operator", Json::str(operator.as_str())),
+                ("left", self.pattern(left)),
+                ("right", self.expression(right))
+            ]),
+            ExpressionKind::Conditional { ref test, ref consequent, ref alternate } => ("ConditionalExpression", vec![
+                ("test", self.expression(test)),
+                ("consequent", self.expression(consequent)),
+                ("alternate", self.expression(alternate))
+            ]),
+            ExpressionKind::Call { ref callee, ref arguments, optional } => ("CallExpression", vec![
+                ("callee", self.expression(callee)),
+                ("arguments", self.expressions(arguments)),
+                ("optional", Json::Bool(optional))
+            ]),
+            ExpressionKind::New { ref callee, ref arguments } => ("NewExpression", vec![
+                ("callee", self.expression(callee)),
+                ("arguments", self.expressions(arguments))
+            ]),
+            ExpressionKind::Member { ref object, ref property, computed, optional } => ("MemberExpression", vec![
+                ("object", self.expression(object)),
+                ("property", self.expression(property)),
+                ("computed", Json::Bool(computed)),
+                ("optional", Json::Bool(optional))
+            ]),
+            ExpressionKind::Chain(ref expression) => ("ChainExpression", vec![
+                ("expression", self.expression(expression))
+            ]),
+            ExpressionKind::Sequence(ref expressions) => ("SequenceExpression", vec![
+                ("expressions", self.expressions(expressions))
+            ]),
+            ExpressionKind::Yield { ref argument, delegate } => ("YieldExpression", vec![
+                ("delegate", Json::Bool(delegate)),
+                ("argument", match *argument {
+                    Some(ref argument) => self.expression(argument),
+                    None => Json::Null
+                })
+            ]),
+            ExpressionKind::Await(ref argument) => ("AwaitExpression", vec![
+                ("argument", self.expression(argument))
+            ]),
+            ExpressionKind::MetaProperty { ref meta, ref property } => ("MetaProperty", vec![
+                ("meta", self.identifier(meta)),
+                ("property", self.identifier(property))
+            ]),
+            ExpressionKind::Template(ref template) => return self.template(template),
+            ExpressionKind::Spread(ref argument) => ("SpreadElement", vec![
+                ("argument", self.expression(argument))
+            ]),
+            ExpressionKind::TaggedTemplate { ref tag, ref quasi } => ("TaggedTemplateExpression", vec![
+                ("tag", self.expression(tag)),
+                ("quasi", self.template(quasi))
+            ])
+        };
+        self.node(node_type, expression.start, expression.end, fields)
+    }
+
+    fn template(&self, template: &TemplateLiteral) -> Json {
+        let quasis = template.quasis.iter().map(|q| {
+            self.node("TemplateElement", q.start, q.end, vec![
+                ("value", Json::Object(vec![("raw", Json::str(&q.raw)), ("cooked", Json::str(&q.cooked))])),
+                ("tail", Json::Bool(q.tail))
+            ])
+        }).collect();
+        self.node("TemplateLiteral", template.start, template.end, vec![
+            ("expressions", self.expressions(&template.expressions)),
+            ("quasis", Json::Array(quasis))
+        ])
+    }
+
+    fn literal(&self, literal: &Literal, expression: &Expression) -> Json {
+        let raw = Json::str(&self.source[expression.start..expression.end]);
+        let fields = match *literal {
+            Literal::Null => vec![("value", Json::Null), ("raw", raw)],
+            Literal::Boolean(value) => vec![("value", Json::Bool(value)), ("raw", raw)],
+            Literal::Number(value) => vec![("value", Json::Number(value)), ("raw", raw)],
+            // like acorn, with the value as a string in `bigint`
+            Literal::BigInt(ref digits) => vec![("value", Json::Null), ("raw", raw), ("bigint", Json::str(digits))],
+            Literal::String(ref value) => vec![("value", Json::str(value)), ("raw", raw)],
+            // a RegExp object can't be represented in JSON
+            Literal::Regexp { ref pattern, ref flags } => vec![
+                ("value", Json::Null),
+                ("raw", raw),
+                ("regex", Json::Object(vec![("pattern", Json::str(pattern)), ("flags", Json::str(flags))]))
+            ]
+        };
+        self.node("Literal", expression.start, expression.end, fields)
+    }
+
+    fn property(&self, property: &Property) -> Json {
+        let mut fields = vec![
+            ("method", Json::Bool(property.method)),
+            ("shorthand", Json::Bool(property.shorthand)),
+            ("computed", Json::Bool(property.computed)),
+            ("key", self.expression(&property.key))
+        ];
+        let kind = ("kind", Json::str(property.kind.as_str()));
+        let value = ("value", self.expression(&property.value));
+        // Acorn sets the kind of accessors, methods and shorthand
+        // properties before parsing their value
+        if property.kind == PropertyKind::Init && !property.shorthand && !property.method {
+            fields.extend([value, kind]);
+        } else {
+            fields.extend([kind, value]);
+        }
+        self.node("Property", property.start, property.end, fields)
+    }
+
+    fn pattern_property(&self, property: &PatternProperty) -> Json {
+        // methods and accessors can't be destructured into, their
+        // function value isn't a pattern
+        let mut fields = vec![
+            ("method", Json::Bool(false)),
+            ("shorthand", Json::Bool(property.shorthand)),
+            ("computed", Json::Bool(property.computed)),
+            ("key", self.expression(&property.key))
+        ];
+        let kind = ("kind", Json::str("init"));
+        let value = ("value", self.pattern(&property.value));
+        // in the same order as in `property`
+        if property.shorthand {
+            fields.extend([kind, value]);
+        } else {
+            fields.extend([value, kind]);
+        }
+        self.node("Property", property.start, property.end, fields)
+    }
+}
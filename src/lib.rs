@@ -0,0 +1,24 @@
+/*
+ * JavaScript tokenizer/parser
+ * Copyright Valentin Vasilyev (valentin.vasilyev@outlook.com) 2014
+ * https://github.com/Valve/tyrion
+ * Ideas heavily borrowed from other JavaScript analyzers/parsers
+ * (esprima/acorn/uglifyjs/typescript compiler)
+ * Code is MIT licensed
+ */
+
+pub use tokenizer::{Tokenizer, Token, TokenType, Options, EcmaVersion, SourceType, ParseResult,
+                    ParseError, ParseErrorKind, KeywordData, ValueData, PuncData, OperatorData,
+                    RegexpValue, Comment, CommentKind};
+pub use location::{Position, SourceLocation};
+pub use parser::Parser;
+pub use estree::to_estree_json;
+pub use diagnostic::render_error;
+
+pub mod ast;
+mod diagnostic;
+mod estree;
+mod location;
+mod parser;
+mod tokenizer;
+mod unicode;
@@ -0,0 +1,75 @@
+// Line and column information for offsets in the source.
+// Columns are counted in UTF-16 code units, like in JavaScript
+// and most editors, so they match what acorn reports.
+
+/// 1-based line and 0-based column
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct SourceLocation {
+    pub start: Position,
+    pub end: Position
+}
+
+// Maps byte offsets to UTF-16 offsets and line/column pairs.
+// Line terminators are '\n', '\r', '\r\n', U+2028 and U+2029.
+pub(crate) struct LineIndex {
+    // byte offsets at which lines start
+    line_starts: Vec<usize>,
+    // (byte offset after a non-ASCII char, bytes minus UTF-16 units so far)
+    utf16_shifts: Vec<(usize, usize)>
+}
+
+impl LineIndex {
+    pub(crate) fn new(source: &str) -> LineIndex {
+        let mut line_starts = vec![0];
+        let mut utf16_shifts = Vec::new();
+        let mut shift = 0;
+        let mut chars = source.char_indices().peekable();
+        while let Some((i, ch)) = chars.next() {
+            if ch.len_utf8() != ch.len_utf16() {
+                shift += ch.len_utf8() - ch.len_utf16();
+                utf16_shifts.push((i + ch.len_utf8(), shift));
+            }
+            match ch {
+                // '\r\n' is a single line break
+                '\r' if matches!(chars.peek(), Some(&(_, '\n'))) => {},
+                '\n' | '\r' | '\u{2028}' | '\u{2029}' => line_starts.push(i + ch.len_utf8()),
+                _ => {}
+            }
+        }
+        LineIndex { line_starts, utf16_shifts }
+    }
+
+    pub(crate) fn utf16_offset(&self, pos: usize) -> usize {
+        let shifts_before = self.utf16_shifts.partition_point(|&(end, _)| end <= pos);
+        match shifts_before {
+            0 => pos,
+            n => pos - self.utf16_shifts[n - 1].1
+        }
+    }
+
+    // 1-based line number of the line containing `pos`
+    pub(crate) fn line(&self, pos: usize) -> usize {
+        self.line_starts.partition_point(|&start| start <= pos)
+    }
+
+    // byte offset at which a 1-based line starts
+    pub(crate) fn line_start(&self, line: usize) -> usize {
+        self.line_starts[line - 1]
+    }
+
+    pub(crate) fn position(&self, pos: usize) -> Position {
+        let line = self.line(pos);
+        let column = self.utf16_offset(pos) - self.utf16_offset(self.line_start(line));
+        Position { line, column }
+    }
+
+    pub(crate) fn location(&self, start: usize, end: usize) -> SourceLocation {
+        SourceLocation { start: self.position(start), end: self.position(end) }
+    }
+}
@@ -0,0 +1,2695 @@
+// Recursive-descent parser building the AST from tokenizer output.
+// The structure closely follows acorn's parser.
+
+use crate::ast::*;
+use crate::location::SourceLocation;
+use crate::tokenizer::{Tokenizer, Token, TokenType, Options, EcmaVersion, ParseError, ParseErrorKind, ParseResult, Comment};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum LabelKind {
+    Loop,
+    Switch,
+    // a label on a statement that is neither a loop nor a switch
+    Plain
+}
+
+// Where a statement is parsed. Module items are only allowed at the
+// top level, and the body of a loop can't be a declaration.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum StatementContext {
+    TopLevel,
+    Nested,
+    LoopBody
+}
+
+struct Label {
+    name: String,
+    kind: LabelKind
+}
+
+// The private names of a class body. They can be used before they are
+// declared, so the uses are checked at the end of the body.
+#[derive(Default)]
+struct PrivateNames {
+    // each declared name, and for a getter or setter without its other
+    // half, its kind and whether it is static
+    declared: Vec<(String, Option<(MethodKind, bool)>)>,
+    used: Vec<(String, usize)>
+}
+
+// Positions of syntax in an array or object literal that is valid
+// either only in an expression or only in a pattern, kept until it is
+// known which of the two the literal is
+#[derive(Debug, Clone, Copy, Default)]
+struct PatternErrors {
+    // `{a = 1}`, only valid in a pattern
+    shorthand_assign: Option<usize>,
+    // a comma after `...a`, only valid in an expression
+    trailing_comma: Option<usize>,
+    // a parenthesized target that isn't a name or member, as in `[(a, b)]`
+    paren_assign: Option<usize>,
+    // any parenthesized target, which can't be bound, as in `((a)) => b`
+    paren_bind: Option<usize>
+}
+
+impl PatternErrors {
+    // Keep the first position of each kind
+    fn merge(&mut self, other: PatternErrors) {
+        self.shorthand_assign = self.shorthand_assign.or(other.shorthand_assign);
+        self.trailing_comma = self.trailing_comma.or(other.trailing_comma);
+        self.paren_assign = self.paren_assign.or(other.paren_assign);
+        self.paren_bind = self.paren_bind.or(other.paren_bind);
+    }
+
+    // Forget the positions inside a member expression starting at
+    // `start`, which is a valid target whatever its object is, as in
+    // `[[...a,].b] = c`. `{a = 1}` is never valid in an expression.
+    fn clear_from(&mut self, start: usize) {
+        for pos in [&mut self.trailing_comma, &mut self.paren_assign, &mut self.paren_bind] {
+            if pos.is_some_and(|pos| pos >= start) {
+                *pos = None;
+            }
+        }
+    }
+}
+
+pub struct Parser<'a> {
+    tokenizer: Tokenizer<'a>,
+    // the current token
+    tok: Token<'a>,
+    // start and end of the previous token, used to finish nodes
+    // and to detect newlines for automatic semicolon insertion
+    last_tok_start: usize,
+    last_tok_end: usize,
+    in_function: bool,
+    // whether `yield` and `await` are operators
+    in_generator: bool,
+    in_async: bool,
+    // whether `super.a` is allowed, in methods, and `super()`, in the
+    // constructor of a derived class
+    allow_super: bool,
+    allow_direct_super: bool,
+    // whether `new.target` is allowed, in functions and the arrow
+    // functions inside them
+    allow_new_target: bool,
+    // inside `static {}` in a class, where `await` and `arguments`
+    // can't be used
+    in_static_block: bool,
+    // the private names of the enclosing class bodies, innermost last
+    private_names: Vec<PrivateNames>,
+    // the start of the expression being parsed by
+    // `parse_maybe_assign`, where an arrow function may start, and
+    // whether the arrow's body may not contain `in`
+    potential_arrow: Option<(usize, bool)>,
+    labels: Vec<Label>,
+    // errors of the array and object literals being parsed
+    pattern_errors: PatternErrors,
+    // index of the first collected comment not yet attached or skipped
+    comment_index: usize,
+    // errors recovered from in tolerant mode
+    errors: Vec<ParseError>
+}
+
+impl<'a> Parser<'a> {
+    pub fn new(input: &'a str, options: Options) -> Parser<'a> {
+        Parser {
+            tokenizer: Tokenizer::new(input, options),
+            tok: Token {
+                value: None,
+                token_type: TokenType::Eof,
+                source: "",
+                start: 0,
+                end: 0,
+                loc: SourceLocation::default(),
+                regex: None,
+                raw: None,
+                number: None,
+                bigint: None,
+                contextual: false
+            },
+            last_tok_start: 0,
+            last_tok_end: 0,
+            in_function: false,
+            in_generator: false,
+            in_async: false,
+            allow_super: false,
+            allow_direct_super: false,
+            allow_new_target: false,
+            in_static_block: false,
+            private_names: Vec::new(),
+            potential_arrow: None,
+            labels: Vec::new(),
+            pattern_errors: PatternErrors::default(),
+            comment_index: 0,
+            errors: Vec::new()
+        }
+    }
+
+    /// Parses the whole input as a program. In tolerant mode,
+    /// statements that fail to parse are left out, see `errors`.
+    pub fn parse(&mut self) -> ParseResult<Program<'a>> {
+        let program = self.parse_program().map_err(|e| self.locate(e))?;
+        self.errors.append(&mut self.tokenizer.take_errors());
+        self.errors.sort_by_key(|e| e.pos);
+        Ok(program)
+    }
+
+    /// Errors recovered from in tolerant mode, in source order
+    pub fn errors(&self) -> &[ParseError] {
+        &self.errors
+    }
+
+    // errors at the current token span the whole token
+    fn locate(&self, e: ParseError) -> ParseError {
+        let end = if e.pos == self.tok.start { self.tok.end } else { e.pos };
+        let loc = self.tokenizer.location(e.pos, end);
+        ParseError { loc, ..e }
+    }
+
+    // Records `e` in tolerant mode, where parsing goes on
+    fn report(&mut self, e: ParseError) -> ParseResult<()> {
+        if !self.tokenizer.options().tolerant {
+            return Err(e)
+        }
+        // an error at the end of a block is also one for the statement
+        // containing it
+        if self.errors.last().is_some_and(|last| last.pos == e.pos && last.kind == e.kind) {
+            return Ok(())
+        }
+        let e = self.locate(e);
+        self.errors.push(e);
+        Ok(())
+    }
+
+    fn parse_program(&mut self) -> ParseResult<Program<'a>> {
+        self.next()?;
+        let body = self.parse_directives_and_statements(true, false)?;
+        let comments = self.tokenizer.comments().to_vec();
+        let source_type = self.tokenizer.options().source_type;
+        Ok(Program { body, source_type, comments, start: 0, end: self.tok.end })
+    }
+
+    // Token helpers
+
+    fn next(&mut self) -> ParseResult<()> {
+        self.last_tok_start = self.tok.start;
+        self.last_tok_end = self.tok.end;
+        self.tok = self.tokenizer.next_token()?;
+        Ok(())
+    }
+
+    fn is_punc(&self, punc: &str) -> bool {
+        matches!(self.tok.token_type, TokenType::Punc(data) if data.punc_type == punc)
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self.tok.token_type, TokenType::Keyword(_)) && self.tok_value() == keyword
+    }
+
+    fn is_operator(&self, op: &str) -> bool {
+        matches!(self.tok.token_type, TokenType::Operator(_)) && self.tok_value() == op
+    }
+
+    // Keywords, literal words like `null` and word operators like `in`
+    // are all reserved, but can still be used as property names
+    fn is_reserved_word(&self) -> bool {
+        match self.tok.token_type {
+            TokenType::Keyword(_) | TokenType::Value(_) => true,
+            TokenType::Operator(_) => self.tok_value().starts_with(|ch: char| ch.is_ascii_alphabetic()),
+            _ => false
+        }
+    }
+
+    // Names like `as` and `from` are only keywords in some places,
+    // and never when written with escapes
+    fn is_contextual(&self, name: &str) -> bool {
+        self.tok.contextual && self.tok_value() == name
+    }
+
+    // A name spelled like a contextual keyword, escaped or not
+    fn is_word(&self, name: &str) -> bool {
+        self.tok.token_type == TokenType::Name && self.tok_value() == name
+    }
+
+    // Contextual keywords can't be written with escapes either, this
+    // is called where one is used as a keyword
+    fn check_unescaped(&self) -> ParseResult<()> {
+        if self.tok.contextual {
+            Ok(())
+        } else {
+            Err(self.raise(ParseErrorKind::EscapedKeyword, self.tok.start))
+        }
+    }
+
+    // `let` starts a declaration if a binding follows it, otherwise
+    // it's a name in sloppy mode. An escaped `let` is a name, but one
+    // followed by a binding on the same line can only be a keyword.
+    fn is_let_declaration(&self) -> bool {
+        if !self.tokenizer.options().is_ecma6() || !self.is_word("let") {
+            return false
+        }
+        let (pos, newline) = self.tokenizer.peek_token_start();
+        if !self.tok.contextual && (newline || self.tokenizer.input()[pos..].starts_with('[')) {
+            return false
+        }
+        if self.tokenizer.input()[pos..].starts_with(['[', '{', '\\']) {
+            return true
+        }
+        !matches!(self.tokenizer.word_at(pos), "" | "in" | "instanceof")
+    }
+
+    // `async` followed by `function` on the same line
+    fn is_async_function(&self) -> bool {
+        if !self.is_word("async") {
+            return false
+        }
+        let (pos, newline) = self.tokenizer.peek_token_start();
+        !newline && self.tokenizer.word_at(pos) == "function"
+    }
+
+    // `import.meta` is an expression, not an import declaration
+    fn is_import_meta(&self) -> bool {
+        let (pos, _) = self.tokenizer.peek_token_start();
+        self.is_keyword("import") && self.tokenizer.input()[pos..].starts_with('.')
+    }
+
+    // Whether the current token can start an expression
+    fn starts_expr(&self) -> bool {
+        match self.tok.token_type {
+            TokenType::Keyword(data) => matches!(data.keyword, "this" | "function" | "new" | "class" | "super" | "import"),
+            TokenType::Punc(data) => matches!(data.punc_type, "(" | "[" | "{" | "`"),
+            TokenType::Operator(data) => data.prefix,
+            TokenType::Eof => false,
+            _ => true
+        }
+    }
+
+    fn eat_contextual(&mut self, name: &str) -> ParseResult<bool> {
+        if self.is_contextual(name) {
+            self.next()?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    fn expect_contextual(&mut self, name: &str) -> ParseResult<()> {
+        if self.eat_contextual(name)? {
+            Ok(())
+        } else if self.is_word(name) {
+            Err(self.raise(ParseErrorKind::EscapedKeyword, self.tok.start))
+        } else {
+            Err(self.unexpected())
+        }
+    }
+
+    fn tok_value(&self) -> &str {
+        self.tok.value.as_deref().unwrap_or("")
+    }
+
+    fn eat_punc(&mut self, punc: &str) -> ParseResult<bool> {
+        if self.is_punc(punc) {
+            self.next()?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    fn expect_punc(&mut self, punc: &str) -> ParseResult<()> {
+        if self.eat_punc(punc)? {
+            Ok(())
+        } else {
+            Err(self.unexpected())
+        }
+    }
+
+    // The `)` closing a statement header and the `}` closing a block
+    // or declaration are followed by a statement, where a '/' starts
+    // a regular expression rather than a division
+    fn expect_punc_before_statement(&mut self, punc: &str) -> ParseResult<()> {
+        if !self.is_punc(punc) {
+            return Err(self.unexpected())
+        }
+        self.tokenizer.allow_regexp();
+        self.next()
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> ParseResult<()> {
+        if self.is_keyword(keyword) {
+            self.next()
+        } else {
+            Err(self.unexpected())
+        }
+    }
+
+    fn unexpected(&self) -> ParseError {
+        // an escaped keyword is read as a name, which is rarely expected
+        if self.tok.token_type == TokenType::Name && self.tokenizer.is_keyword(self.tok_value()) {
+            return ParseError::new(ParseErrorKind::EscapedKeyword, self.tok.start)
+        }
+        ParseError::new(ParseErrorKind::UnexpectedToken, self.tok.start)
+    }
+
+    fn raise(&self, kind: ParseErrorKind, pos: usize) -> ParseError {
+        ParseError::new(kind, pos)
+    }
+
+    // Test whether a semicolon can be inserted at the current position
+    fn can_insert_semicolon(&self) -> bool {
+        matches!(self.tok.token_type, TokenType::Eof) || self.is_punc("}") ||
+            self.newline_before_token()
+    }
+
+    fn newline_before_token(&self) -> bool {
+        self.tokenizer.input()[self.last_tok_end..self.tok.start]
+            .chars().any(|ch| matches!(ch, '\n' | '\r' | '\u{2028}' | '\u{2029}'))
+    }
+
+    // Consume a semicolon, or, failing that, see if we are allowed to
+    // pretend that there is a semicolon at this position.
+    fn semicolon(&mut self) -> ParseResult<()> {
+        if !self.eat_punc(";")? && !self.can_insert_semicolon() {
+            return Err(self.unexpected())
+        }
+        Ok(())
+    }
+
+    fn statement(&self, kind: StatementKind<'a>, start: usize) -> Statement<'a> {
+        Statement {
+            kind,
+            leading_comments: Vec::new(),
+            trailing_comments: Vec::new(),
+            start,
+            end: self.last_tok_end
+        }
+    }
+
+    fn expression(&self, kind: ExpressionKind<'a>, start: usize) -> Expression<'a> {
+        Expression { kind, start, end: self.last_tok_end }
+    }
+
+    // Statements
+
+    // Parses statements up to '}' (or the end of input at top level),
+    // handling the "use strict" directive prologue.
+    fn parse_directives_and_statements(&mut self, top_level: bool, allow_strict: bool) -> ParseResult<Vec<Statement<'a>>> {
+        let mut body = Vec::new();
+        let mut in_prologue = true;
+        let prologue_start = self.tok.start;
+        loop {
+            let at_end = if top_level {
+                matches!(self.tok.token_type, TokenType::Eof)
+            } else {
+                self.is_punc("}")
+            };
+            if at_end { break; }
+            if matches!(self.tok.token_type, TokenType::Eof) {
+                return Err(self.unexpected())
+            }
+            let is_directive_candidate = in_prologue &&
+                matches!(self.tok.token_type, TokenType::StringLiteral);
+            let raw_start = self.tok.start;
+            let raw_end = self.tok.end;
+            let context = if top_level { StatementContext::TopLevel } else { StatementContext::Nested };
+            let statement = match self.parse_statement_tolerant(context)? {
+                Some(statement) => statement,
+                None => {
+                    in_prologue = false;
+                    continue;
+                }
+            };
+            if is_directive_candidate {
+                if let StatementKind::Expression(Expression { kind: ExpressionKind::Literal(Literal::String(_)), start, end }) = statement.kind {
+                    // only an unescaped, unparenthesized string counts as a directive
+                    // ES3 has no strict mode
+                    if start == raw_start && end == raw_end && self.tokenizer.options().at_least(EcmaVersion::Ecma5) &&
+                        self.tokenizer.input().get(start + 1..end - 1) == Some("use strict") && (top_level || allow_strict) {
+                        // the prologue and the token after it were read in sloppy mode
+                        if let Some(e) = self.tokenizer.last_strict_mode_error().filter(|e| e.pos >= prologue_start) {
+                            self.report(e)?;
+                        }
+                        self.tokenizer.set_strict(true);
+                    }
+                } else {
+                    in_prologue = false;
+                }
+            } else {
+                in_prologue = false;
+            }
+            body.push(statement);
+        }
+        Ok(body)
+    }
+
+    // Comments
+
+    // Collected comments between the previous and the current token.
+    // Comments before that weren't attached to a statement, so they're skipped.
+    fn take_leading_comments(&mut self) -> Vec<Comment<'a>> {
+        let comments = self.tokenizer.comments();
+        let mut leading = Vec::new();
+        while let Some(comment) = comments.get(self.comment_index) {
+            if comment.end > self.tok.start { break; }
+            if comment.start >= self.last_tok_end {
+                leading.push(comment.clone());
+            }
+            self.comment_index += 1;
+        }
+        leading
+    }
+
+    // Collected comments after `end` up to the current token,
+    // that are on the line `end` is on.
+    fn take_trailing_comments(&mut self, end: usize) -> Vec<Comment<'a>> {
+        let comments = self.tokenizer.comments();
+        let mut trailing = Vec::new();
+        while let Some(comment) = comments.get(self.comment_index) {
+            if comment.start < end {
+                // inside the statement
+                self.comment_index += 1;
+                continue;
+            }
+            let on_same_line = !self.tokenizer.input()[end..comment.start]
+                .contains(['\n', '\r', '\u{2028}', '\u{2029}']);
+            if comment.end > self.tok.start || !on_same_line { break; }
+            trailing.push(comment.clone());
+            self.comment_index += 1;
+        }
+        trailing
+    }
+
+    // Parse a single statement, attaching comments around it
+    fn parse_statement(&mut self, context: StatementContext) -> ParseResult<Statement<'a>> {
+        let leading_comments = self.take_leading_comments();
+        let statement = self.parse_statement_kind(context)?;
+        let trailing_comments = self.take_trailing_comments(statement.end);
+        Ok(Statement { leading_comments, trailing_comments, ..statement })
+    }
+
+    // In tolerant mode, a statement that fails to parse is
+    // recorded and skipped, and `None` returned for it
+    fn parse_statement_tolerant(&mut self, context: StatementContext) -> ParseResult<Option<Statement<'a>>> {
+        let start = self.tok.start;
+        let e = match self.parse_statement(context) {
+            Ok(statement) => return Ok(Some(statement)),
+            Err(e) => e
+        };
+        // the tokenizer already reported invalid tokens
+        let at_invalid = matches!(self.tok.token_type, TokenType::Invalid) && e.pos == self.tok.start;
+        if !at_invalid {
+            self.report(e)?;
+        }
+        self.skip_statement(start)?;
+        Ok(None)
+    }
+
+    // Skips the rest of a statement that failed to parse: up to and
+    // including the next ';', or up to a '}' or a token on a new line,
+    // keeping braces balanced.
+    fn skip_statement(&mut self, start: usize) -> ParseResult<()> {
+        // always make progress
+        if self.tok.start == start && !matches!(self.tok.token_type, TokenType::Eof) {
+            self.next()?;
+        }
+        let mut depth = 0;
+        loop {
+            match self.tok.token_type {
+                TokenType::Eof => break,
+                TokenType::Punc(data) => match data.punc_type {
+                    "{" => depth += 1,
+                    "}" if depth == 0 => break,
+                    "}" => depth -= 1,
+                    ";" if depth == 0 => {
+                        self.next()?;
+                        break;
+                    },
+                    _ if depth == 0 && self.newline_before_token() => break,
+                    _ => {}
+                },
+                _ if depth == 0 && self.newline_before_token() => break,
+                _ => {}
+            }
+            self.next()?;
+        }
+        Ok(())
+    }
+
+    fn parse_statement_kind(&mut self, context: StatementContext) -> ParseResult<Statement<'a>> {
+        let start = self.tok.start;
+        if context == StatementContext::LoopBody && self.is_loop_body_declaration() {
+            return Err(self.unexpected())
+        }
+        if let TokenType::Keyword(_) = self.tok.token_type {
+            match self.tok_value() {
+                "import" | "export" if !self.is_import_meta() => {
+                    if !self.tokenizer.options().is_module() {
+                        return Err(self.raise(ParseErrorKind::ImportExportOutsideModule, start))
+                    }
+                    if context != StatementContext::TopLevel {
+                        return Err(self.raise(ParseErrorKind::ImportExportNotTopLevel, start))
+                    }
+                    return if self.is_keyword("import") { self.parse_import(start) } else { self.parse_export(start) }
+                },
+                "break" => return self.parse_break_continue(start, true),
+                "continue" => return self.parse_break_continue(start, false),
+                "debugger" => {
+                    self.next()?;
+                    self.semicolon()?;
+                    return Ok(self.statement(StatementKind::Debugger, start))
+                },
+                "do" => return self.parse_do_while(start),
+                "for" => return self.parse_for(start),
+                "const" => return self.parse_var_statement(start, VariableKind::Const),
+                "function" => {
+                    self.next()?;
+                    let function = self.parse_function(start, true, false, false)?;
+                    return Ok(self.statement(StatementKind::FunctionDeclaration(function), start))
+                },
+                "class" => {
+                    let class = self.parse_class(true, false)?;
+                    return Ok(self.statement(StatementKind::ClassDeclaration(class), start))
+                },
+                "if" => return self.parse_if(start),
+                "return" => return self.parse_return(start),
+                "switch" => return self.parse_switch(start),
+                "throw" => return self.parse_throw(start),
+                "try" => return self.parse_try(start),
+                "var" => return self.parse_var_statement(start, VariableKind::Var),
+                "while" => return self.parse_while(start),
+                "with" => return self.parse_with(start),
+                _ => {}
+            }
+        }
+        // in a loop body, `let` followed by a newline and a name is an
+        // expression, the name starts the next statement
+        if self.is_let_declaration() && context != StatementContext::LoopBody {
+            return self.parse_var_statement(start, VariableKind::Let)
+        }
+        if self.is_async_function() {
+            let function = self.parse_async_function(true, false)?;
+            return Ok(self.statement(StatementKind::FunctionDeclaration(function), start))
+        }
+        if self.is_punc("{") {
+            let block = self.parse_block()?;
+            return Ok(self.statement(StatementKind::Block(block), start))
+        }
+        if self.is_punc(";") {
+            self.next()?;
+            return Ok(self.statement(StatementKind::Empty, start))
+        }
+        // If the statement does not start with a statement keyword or a
+        // brace, it's an expression statement or labeled statement.
+        // A label is a single name, `(a): b` is not a labeled statement.
+        let starts_with_name = matches!(self.tok.token_type, TokenType::Name);
+        let expr = self.parse_expression(false)?;
+        if let ExpressionKind::Identifier(ref name) = expr.kind {
+            if starts_with_name && self.is_punc(":") {
+                let label = Identifier { name: name.clone(), start: expr.start, end: expr.end };
+                return self.parse_labeled_statement(start, label)
+            }
+        }
+        self.semicolon()?;
+        Ok(self.statement(StatementKind::Expression(expr), start))
+    }
+
+    fn parse_var_statement(&mut self, start: usize, kind: VariableKind) -> ParseResult<Statement<'a>> {
+        if kind == VariableKind::Let {
+            self.check_unescaped()?;
+        }
+        self.next()?;
+        let declaration = self.parse_var(start, false, kind)?;
+        self.semicolon()?;
+        let declaration = VariableDeclaration { end: self.last_tok_end, ..declaration };
+        Ok(self.statement(StatementKind::VariableDeclaration(declaration), start))
+    }
+
+    fn parse_break_continue(&mut self, start: usize, is_break: bool) -> ParseResult<Statement<'a>> {
+        self.next()?;
+        let label = if self.eat_punc(";")? || self.can_insert_semicolon() {
+            None
+        } else if let TokenType::Name = self.tok.token_type {
+            let label = self.parse_ident(false)?;
+            self.semicolon()?;
+            Some(label)
+        } else {
+            return Err(self.unexpected())
+        };
+        // Verify that there is an actual destination to break or
+        // continue to.
+        let found = self.labels.iter().rev().any(|l| {
+            match label {
+                None => l.kind == LabelKind::Loop || (is_break && l.kind == LabelKind::Switch),
+                Some(ref label) => l.name == label.name && (is_break || l.kind == LabelKind::Loop)
+            }
+        });
+        if !found {
+            let kind = if is_break { ParseErrorKind::UnsyntacticBreak } else { ParseErrorKind::UnsyntacticContinue };
+            return Err(self.raise(kind, start))
+        }
+        let kind = if is_break { StatementKind::Break(label) } else { StatementKind::Continue(label) };
+        Ok(self.statement(kind, start))
+    }
+
+    fn parse_do_while(&mut self, start: usize) -> ParseResult<Statement<'a>> {
+        self.next()?;
+        let body = self.parse_loop_body()?;
+        self.expect_keyword("while")?;
+        let test = self.parse_paren_expression()?;
+        // a semicolon after do-while is always optional
+        self.eat_punc(";")?;
+        Ok(self.statement(StatementKind::DoWhile { body: Box::new(body), test }, start))
+    }
+
+    // Disambiguating between a `for` and a `for`/`in` or `for`/`of`
+    // loop is non-trivial. Basically, we have to parse the init `var`
+    // statement or expression, disallowing the `in` operator, and
+    // then check whether the next token is `in` or `of`.
+    fn parse_for(&mut self, start: usize) -> ParseResult<Statement<'a>> {
+        self.next()?;
+        // `for await (a of b)` in async functions and modules
+        let await_start = if self.is_contextual("await") && self.can_await()? {
+            self.tokenizer.require_version("for-await-of", EcmaVersion::Ecma2018, self.tok.start)?;
+            self.next()?;
+            Some(self.last_tok_start)
+        } else {
+            None
+        };
+        self.expect_punc("(")?;
+        if self.is_punc(";") {
+            return self.parse_for_rest(start, None, await_start)
+        }
+        let kind = if self.is_keyword("var") {
+            Some(VariableKind::Var)
+        } else if self.is_keyword("const") {
+            Some(VariableKind::Const)
+        } else if self.is_let_declaration() {
+            self.check_unescaped()?;
+            Some(VariableKind::Let)
+        } else {
+            None
+        };
+        if let Some(kind) = kind {
+            let init_start = self.tok.start;
+            self.next()?;
+            let declaration = self.parse_var(init_start, true, kind)?;
+            if declaration.declarations.len() == 1 && (self.is_in_operator() || self.is_contextual("of")) {
+                return self.parse_for_in(start, ForInit::VariableDeclaration(declaration), await_start)
+            }
+            return self.parse_for_rest(start, Some(ForInit::VariableDeclaration(declaration)), await_start)
+        }
+        let (init, errors) = self.with_pattern_errors(|p| p.parse_expression_or_pattern(true))?;
+        if self.is_in_operator() || self.is_contextual("of") {
+            let left = self.to_assignment_target(init, errors)?;
+            return self.parse_for_in(start, ForInit::Pattern(left), await_start)
+        }
+        self.check_expression_errors(errors)?;
+        self.parse_for_rest(start, Some(ForInit::Expression(init)), await_start)
+    }
+
+    // Parse a regular `for` loop. The disambiguation code in
+    // `parse_for` will already have parsed the init statement or
+    // expression. `await_start` is where a misplaced `await` is.
+    fn parse_for_rest(&mut self, start: usize, init: Option<ForInit<'a>>, await_start: Option<usize>) -> ParseResult<Statement<'a>> {
+        if let Some(pos) = await_start {
+            return Err(self.raise(ParseErrorKind::UnexpectedToken, pos))
+        }
+        // an unescaped `of` would have started a `for`/`of` loop
+        if self.is_word("of") {
+            return Err(self.raise(ParseErrorKind::EscapedKeyword, self.tok.start))
+        }
+        self.expect_punc(";")?;
+        let test = if self.is_punc(";") { None } else { Some(self.parse_expression(false)?) };
+        self.expect_punc(";")?;
+        let update = if self.is_punc(")") { None } else { Some(self.parse_expression(false)?) };
+        self.expect_punc_before_statement(")")?;
+        let body = self.parse_loop_body()?;
+        Ok(self.statement(StatementKind::For { init, test, update, body: Box::new(body) }, start))
+    }
+
+    // Parse a `for`/`in` or `for`/`of` loop, at the `in` or `of`.
+    // Only the latter can have an `await` at `await_start`.
+    fn parse_for_in(&mut self, start: usize, left: ForInit<'a>, await_start: Option<usize>) -> ParseResult<Statement<'a>> {
+        let is_of = self.is_contextual("of");
+        if is_of {
+            self.tokenizer.require_version("for-of", EcmaVersion::Ecma6, self.tok.start)?;
+        } else if let Some(pos) = await_start {
+            return Err(self.raise(ParseErrorKind::UnexpectedToken, pos))
+        }
+        if let ForInit::VariableDeclaration(ref declaration) = left {
+            // `for (var x = 1 in y)` is still allowed in sloppy mode
+            let declarator = &declaration.declarations[0];
+            let simple = matches!(declarator.id.kind, PatternKind::Identifier(_));
+            if declarator.init.is_some() && (is_of || declaration.kind != VariableKind::Var || self.tokenizer.is_strict() || !simple) {
+                let kind = ParseErrorKind::ForLoopInitializer(if is_of { "for-of" } else { "for-in" });
+                return Err(self.raise(kind, declaration.start))
+            }
+        }
+        self.next()?;
+        let right = if is_of { self.parse_maybe_assign(false)? } else { self.parse_expression(false)? };
+        self.expect_punc_before_statement(")")?;
+        let body = Box::new(self.parse_loop_body()?);
+        let kind = if is_of {
+            StatementKind::ForOf { left, right, body, is_await: await_start.is_some() }
+        } else {
+            StatementKind::ForIn { left, right, body }
+        };
+        Ok(self.statement(kind, start))
+    }
+
+    fn parse_loop_body(&mut self) -> ParseResult<Statement<'a>> {
+        self.labels.push(Label { name: String::new(), kind: LabelKind::Loop });
+        let body = self.parse_statement(StatementContext::LoopBody);
+        self.labels.pop();
+        body
+    }
+
+    // A loop body is a single statement, which can't be a declaration
+    // other than `var`, or since ES6 a function. `let [` always starts
+    // a declaration, `let` followed by a name only on the same line.
+    fn is_loop_body_declaration(&self) -> bool {
+        if let TokenType::Keyword(data) = self.tok.token_type {
+            return matches!(data.keyword, "class" | "const") || data.keyword == "function" && self.tokenizer.options().is_ecma6()
+        }
+        if self.is_let_declaration() {
+            let (pos, newline) = self.tokenizer.peek_token_start();
+            return !newline || self.tokenizer.input()[pos..].starts_with(['[', '\\'])
+        }
+        self.is_async_function()
+    }
+
+    fn parse_if(&mut self, start: usize) -> ParseResult<Statement<'a>> {
+        self.next()?;
+        let test = self.parse_paren_expression()?;
+        let consequent = Box::new(self.parse_statement(StatementContext::Nested)?);
+        let alternate = if self.is_keyword("else") {
+            self.next()?;
+            Some(Box::new(self.parse_statement(StatementContext::Nested)?))
+        } else {
+            None
+        };
+        Ok(self.statement(StatementKind::If { test, consequent, alternate }, start))
+    }
+
+    fn parse_return(&mut self, start: usize) -> ParseResult<Statement<'a>> {
+        if !self.in_function {
+            return Err(self.raise(ParseErrorKind::ReturnOutsideFunction, start))
+        }
+        self.next()?;
+        // In `return` (and `break`/`continue`), the keywords with
+        // optional arguments, we eagerly look for a semicolon or the
+        // possibility to insert one.
+        let argument = if self.eat_punc(";")? || self.can_insert_semicolon() {
+            None
+        } else {
+            let argument = self.parse_expression(false)?;
+            self.semicolon()?;
+            Some(argument)
+        };
+        Ok(self.statement(StatementKind::Return(argument), start))
+    }
+
+    fn parse_switch(&mut self, start: usize) -> ParseResult<Statement<'a>> {
+        self.next()?;
+        let discriminant = self.parse_paren_expression()?;
+        self.expect_punc("{")?;
+        self.labels.push(Label { name: String::new(), kind: LabelKind::Switch });
+        let cases = self.parse_switch_cases();
+        self.labels.pop();
+        Ok(self.statement(StatementKind::Switch { discriminant, cases: cases? }, start))
+    }
+
+    fn parse_switch_cases(&mut self) -> ParseResult<Vec<SwitchCase<'a>>> {
+        let mut cases: Vec<SwitchCase<'a>> = Vec::new();
+        let mut saw_default = false;
+        while !self.is_punc("}") {
+            let case_start = self.tok.start;
+            let test = if self.is_keyword("case") {
+                self.next()?;
+                Some(self.parse_expression(false)?)
+            } else if self.is_keyword("default") {
+                if saw_default {
+                    return Err(self.raise(ParseErrorKind::MultipleDefaultClauses, case_start))
+                }
+                saw_default = true;
+                self.next()?;
+                None
+            } else {
+                return Err(self.unexpected())
+            };
+            self.expect_punc(":")?;
+            let mut consequent = Vec::new();
+            while !self.is_punc("}") && !self.is_keyword("case") && !self.is_keyword("default") {
+                consequent.push(self.parse_statement(StatementContext::Nested)?);
+            }
+            cases.push(SwitchCase { test, consequent, start: case_start, end: self.last_tok_end });
+        }
+        self.expect_punc_before_statement("}")?;
+        Ok(cases)
+    }
+
+    fn parse_throw(&mut self, start: usize) -> ParseResult<Statement<'a>> {
+        self.next()?;
+        if self.newline_before_token() {
+            return Err(self.raise(ParseErrorKind::IllegalNewlineAfterThrow, self.last_tok_end))
+        }
+        let argument = self.parse_expression(false)?;
+        self.semicolon()?;
+        Ok(self.statement(StatementKind::Throw(argument), start))
+    }
+
+    fn parse_try(&mut self, start: usize) -> ParseResult<Statement<'a>> {
+        self.next()?;
+        let block = self.parse_block()?;
+        let handler = if self.is_keyword("catch") {
+            let clause_start = self.tok.start;
+            self.next()?;
+            let param = if self.is_punc("{") {
+                self.tokenizer.require_version("Optional catch binding", EcmaVersion::Ecma2019, self.tok.start)?;
+                None
+            } else {
+                self.expect_punc("(")?;
+                let param = self.parse_binding_atom()?;
+                self.expect_punc(")")?;
+                Some(param)
+            };
+            let body = self.parse_block()?;
+            Some(CatchClause { param, body, start: clause_start, end: self.last_tok_end })
+        } else {
+            None
+        };
+        let finalizer = if self.is_keyword("finally") {
+            self.next()?;
+            Some(self.parse_block()?)
+        } else {
+            None
+        };
+        if handler.is_none() && finalizer.is_none() {
+            return Err(self.raise(ParseErrorKind::MissingCatchOrFinally, start))
+        }
+        Ok(self.statement(StatementKind::Try { block, handler, finalizer }, start))
+    }
+
+    fn parse_while(&mut self, start: usize) -> ParseResult<Statement<'a>> {
+        self.next()?;
+        let test = self.parse_paren_expression()?;
+        let body = self.parse_loop_body()?;
+        Ok(self.statement(StatementKind::While { test, body: Box::new(body) }, start))
+    }
+
+    fn parse_with(&mut self, start: usize) -> ParseResult<Statement<'a>> {
+        if self.tokenizer.is_strict() {
+            return Err(self.raise(ParseErrorKind::WithInStrictMode, start))
+        }
+        self.next()?;
+        let object = self.parse_paren_expression()?;
+        let body = Box::new(self.parse_statement(StatementContext::Nested)?);
+        Ok(self.statement(StatementKind::With { object, body }, start))
+    }
+
+    fn parse_labeled_statement(&mut self, start: usize, label: Identifier) -> ParseResult<Statement<'a>> {
+        if self.labels.iter().any(|l| l.name == label.name) {
+            return Err(self.raise(ParseErrorKind::LabelAlreadyDeclared, label.start))
+        }
+        self.next()?; // ':'
+        let kind = if self.is_keyword("for") || self.is_keyword("while") || self.is_keyword("do") {
+            LabelKind::Loop
+        } else if self.is_keyword("switch") {
+            LabelKind::Switch
+        } else {
+            LabelKind::Plain
+        };
+        self.labels.push(Label { name: label.name.clone(), kind });
+        let body = self.parse_statement(StatementContext::Nested);
+        self.labels.pop();
+        let body = Box::new(body?);
+        Ok(self.statement(StatementKind::Labeled { label, body }, start))
+    }
+
+    // Modules
+
+    // Parses `import` declarations, the module specifier comes last:
+    // `import def, { a as b } from "mod"`, `import * as ns from "mod"`
+    // or just `import "mod"`.
+    fn parse_import(&mut self, start: usize) -> ParseResult<Statement<'a>> {
+        self.next()?;
+        let mut specifiers = Vec::new();
+        if !matches!(self.tok.token_type, TokenType::StringLiteral) {
+            if let TokenType::Name = self.tok.token_type {
+                let local = self.parse_binding_ident()?;
+                specifiers.push(ImportSpecifier {
+                    kind: ImportSpecifierKind::Default,
+                    start: local.start,
+                    end: local.end,
+                    local
+                });
+                if self.eat_punc(",")? {
+                    self.parse_import_bindings(&mut specifiers)?;
+                }
+            } else {
+                self.parse_import_bindings(&mut specifiers)?;
+            }
+            self.expect_contextual("from")?;
+        }
+        let source = self.parse_module_source()?;
+        self.semicolon()?;
+        Ok(self.statement(StatementKind::Import { specifiers, source }, start))
+    }
+
+    // Parses `* as ns` or `{ a, b as c }`
+    fn parse_import_bindings(&mut self, specifiers: &mut Vec<ImportSpecifier>) -> ParseResult<()> {
+        let start = self.tok.start;
+        if self.is_operator("*") {
+            self.next()?;
+            self.expect_contextual("as")?;
+            let local = self.parse_binding_ident()?;
+            specifiers.push(ImportSpecifier { kind: ImportSpecifierKind::Namespace, local, start, end: self.last_tok_end });
+            return Ok(())
+        }
+        self.expect_punc("{")?;
+        let mut first = true;
+        while !self.eat_punc("}")? {
+            if !first {
+                self.expect_punc(",")?;
+                if self.eat_punc("}")? { break; }
+            } else {
+                first = false;
+            }
+            let start = self.tok.start;
+            // keywords can be imported, but not bound
+            let is_keyword = self.is_reserved_word();
+            let imported = self.parse_ident(true)?;
+            let local = if self.eat_contextual("as")? {
+                self.parse_binding_ident()?
+            } else if is_keyword {
+                return Err(self.raise(ParseErrorKind::UnexpectedToken, imported.start))
+            } else {
+                imported.clone()
+            };
+            specifiers.push(ImportSpecifier { kind: ImportSpecifierKind::Named(imported), local, start, end: self.last_tok_end });
+        }
+        Ok(())
+    }
+
+    fn parse_export(&mut self, start: usize) -> ParseResult<Statement<'a>> {
+        self.next()?;
+        // export * from "mod"
+        if self.is_operator("*") {
+            self.next()?;
+            let exported = if self.eat_contextual("as")? { Some(self.parse_ident(true)?) } else { None };
+            self.expect_contextual("from")?;
+            let source = self.parse_module_source()?;
+            self.semicolon()?;
+            return Ok(self.statement(StatementKind::ExportAll { exported, source }, start))
+        }
+        // export default ...
+        if self.is_keyword("default") {
+            self.next()?;
+            let declaration = if self.is_keyword("function") {
+                let function_start = self.tok.start;
+                self.next()?;
+                ExportDefault::Function(self.parse_function(function_start, true, true, false)?)
+            } else if self.is_async_function() {
+                ExportDefault::Function(self.parse_async_function(true, true)?)
+            } else if self.is_keyword("class") {
+                ExportDefault::Class(self.parse_class(true, true)?)
+            } else {
+                let expr = self.parse_maybe_assign(false)?;
+                self.semicolon()?;
+                ExportDefault::Expression(expr)
+            };
+            return Ok(self.statement(StatementKind::ExportDefault(declaration), start))
+        }
+        // export var ...
+        if self.is_keyword("var") || self.is_keyword("const") || self.is_keyword("function") ||
+            self.is_keyword("class") || self.is_let_declaration() || self.is_async_function() {
+            let declaration = Some(Box::new(self.parse_statement_kind(StatementContext::Nested)?));
+            let kind = StatementKind::ExportNamed { declaration, specifiers: Vec::new(), source: None };
+            return Ok(self.statement(kind, start))
+        }
+        // export { x, y as z } [from "mod"]
+        self.expect_punc("{")?;
+        let mut specifiers = Vec::new();
+        // the first keyword exported, which needs a `from` clause
+        let mut keyword_start = None;
+        let mut first = true;
+        while !self.eat_punc("}")? {
+            if !first {
+                self.expect_punc(",")?;
+                if self.eat_punc("}")? { break; }
+            } else {
+                first = false;
+            }
+            let start = self.tok.start;
+            if keyword_start.is_none() && self.is_reserved_word() {
+                keyword_start = Some(start);
+            }
+            let local = self.parse_ident(true)?;
+            let exported = if self.eat_contextual("as")? { self.parse_ident(true)? } else { local.clone() };
+            specifiers.push(ExportSpecifier { local, exported, start, end: self.last_tok_end });
+        }
+        let source = if self.eat_contextual("from")? {
+            Some(self.parse_module_source()?)
+        } else if let Some(keyword_start) = keyword_start {
+            return Err(self.raise(ParseErrorKind::UnexpectedToken, keyword_start))
+        } else {
+            None
+        };
+        self.semicolon()?;
+        Ok(self.statement(StatementKind::ExportNamed { declaration: None, specifiers, source }, start))
+    }
+
+    fn parse_module_source(&mut self) -> ParseResult<Expression<'a>> {
+        if let TokenType::StringLiteral = self.tok.token_type {
+            self.parse_expr_atom()
+        } else {
+            Err(self.unexpected())
+        }
+    }
+
+    // Parse a semicolon-enclosed block of statements
+    fn parse_block(&mut self) -> ParseResult<Block<'a>> {
+        let start = self.tok.start;
+        self.expect_punc("{")?;
+        let mut body = Vec::new();
+        while !self.is_punc("}") {
+            if matches!(self.tok.token_type, TokenType::Eof) {
+                return Err(self.unexpected())
+            }
+            body.extend(self.parse_statement_tolerant(StatementContext::Nested)?);
+        }
+        self.expect_punc_before_statement("}")?;
+        Ok(Block { body, start, end: self.last_tok_end })
+    }
+
+    // Parse a list of variable declarations, `no_in` is set
+    // in the init part of a `for` loop.
+    fn parse_var(&mut self, start: usize, no_in: bool, kind: VariableKind) -> ParseResult<VariableDeclaration<'a>> {
+        let mut declarations = Vec::new();
+        loop {
+            let id = self.parse_binding_atom()?;
+            if kind != VariableKind::Var {
+                if let Some(&(_, pos)) = id.bound_names().iter().find(|&&(name, _)| name == "let") {
+                    return Err(self.raise(ParseErrorKind::LetInLexicalBinding, pos))
+                }
+            }
+            let in_for_in = no_in && (self.is_in_operator() || self.is_contextual("of"));
+            let init = if self.is_operator("=") {
+                self.next()?;
+                Some(self.parse_maybe_assign(no_in)?)
+            } else if kind == VariableKind::Const && !in_for_in {
+                return Err(self.unexpected())
+            } else if !matches!(id.kind, PatternKind::Identifier(_)) && !in_for_in {
+                return Err(self.raise(ParseErrorKind::MissingPatternInitializer, self.last_tok_end))
+            } else {
+                None
+            };
+            declarations.push(VariableDeclarator { start: id.start, id, init, end: self.last_tok_end });
+            if !self.eat_punc(",")? { break; }
+        }
+        Ok(VariableDeclaration { declarations, kind, start, end: self.last_tok_end })
+    }
+
+    // Expressions
+
+    // Parse a full expression. The argument is used to forbid the
+    // `in` operator (in for loops initialization expressions).
+    fn parse_expression(&mut self, no_in: bool) -> ParseResult<Expression<'a>> {
+        let (expr, errors) = self.with_pattern_errors(|p| p.parse_expression_or_pattern(no_in))?;
+        self.check_expression_errors(errors)?;
+        Ok(expr)
+    }
+
+    // Like `parse_expression`, but an array or object literal may still
+    // turn out to be a pattern, so its errors are left in
+    // `pattern_errors`
+    fn parse_expression_or_pattern(&mut self, no_in: bool) -> ParseResult<Expression<'a>> {
+        let start = self.tok.start;
+        let expr = self.parse_maybe_assign_or_pattern(no_in)?;
+        if self.is_punc(",") {
+            let mut expressions = vec![expr];
+            while self.eat_punc(",")? {
+                expressions.push(self.parse_maybe_assign_or_pattern(no_in)?);
+            }
+            return Ok(self.expression(ExpressionKind::Sequence(expressions), start))
+        }
+        Ok(expr)
+    }
+
+    fn parse_maybe_assign(&mut self, no_in: bool) -> ParseResult<Expression<'a>> {
+        let (expr, errors) = self.with_pattern_errors(|p| p.parse_maybe_assign_or_pattern(no_in))?;
+        self.check_expression_errors(errors)?;
+        Ok(expr)
+    }
+
+    // Parse an assignment expression. This includes applications of
+    // operators like `+=`. An array or object literal on the left of
+    // `=` is converted to a destructuring pattern.
+    fn parse_maybe_assign_or_pattern(&mut self, no_in: bool) -> ParseResult<Expression<'a>> {
+        if self.in_generator && self.is_contextual("yield") {
+            return self.parse_yield(no_in)
+        }
+        let start = self.tok.start;
+        self.potential_arrow = Some((start, no_in));
+        let (left, errors) = self.with_pattern_errors(|p| p.parse_maybe_conditional(no_in))?;
+        if let TokenType::Operator(data) = self.tok.token_type {
+            if data.is_assign {
+                let operator = AssignmentOperator::from_op(self.tok_value()).ok_or_else(|| self.unexpected())?;
+                let left = if operator == AssignmentOperator::Assign {
+                    self.to_assignment_target(left, errors)?
+                } else {
+                    self.check_lvalue(&left)?;
+                    self.to_pattern(left)?
+                };
+                self.next()?;
+                let right = self.parse_maybe_assign(no_in)?;
+                let kind = ExpressionKind::Assignment { operator, left: Box::new(left), right: Box::new(right) };
+                return Ok(self.expression(kind, start))
+            }
+        }
+        self.pattern_errors.merge(errors);
+        Ok(left)
+    }
+
+    // Run `parse` with no pattern errors recorded, and return the ones
+    // it leaves
+    fn with_pattern_errors<T>(&mut self, parse: impl FnOnce(&mut Self) -> ParseResult<T>) -> ParseResult<(T, PatternErrors)> {
+        let outer = std::mem::take(&mut self.pattern_errors);
+        let result = parse(self);
+        let errors = std::mem::replace(&mut self.pattern_errors, outer);
+        Ok((result?, errors))
+    }
+
+    // The literals turned out to be expressions
+    fn check_expression_errors(&self, errors: PatternErrors) -> ParseResult<()> {
+        match errors.shorthand_assign {
+            Some(pos) => Err(self.raise(ParseErrorKind::ShorthandPropertyAssign, pos)),
+            None => Ok(())
+        }
+    }
+
+    // Convert the target of `=` or of a `for`-`in`/`for`-`of` loop to a
+    // pattern, an array or object literal must not have contained
+    // syntax that is only valid in an expression
+    fn to_assignment_target(&self, expr: Expression<'a>, errors: PatternErrors) -> ParseResult<Pattern<'a>> {
+        if !matches!(expr.kind, ExpressionKind::Array(_) | ExpressionKind::Object(_)) {
+            self.check_lvalue(&expr)?;
+        } else if let Some(pos) = errors.trailing_comma {
+            return Err(self.raise(ParseErrorKind::CommaAfterRestElement, pos))
+        } else if let Some(pos) = errors.paren_assign {
+            return Err(self.raise(ParseErrorKind::AssigningToRvalue, pos))
+        }
+        self.to_pattern(expr)
+    }
+
+    // Convert an expression to the pattern it covers
+    fn to_pattern(&self, expr: Expression<'a>) -> ParseResult<Pattern<'a>> {
+        let Expression { kind, start, end } = expr;
+        let kind = match kind {
+            ExpressionKind::Identifier(name) => {
+                self.tokenizer.check_identifier(&name, start, self.tokenizer.is_strict(), true)?;
+                PatternKind::Identifier(name)
+            },
+            ExpressionKind::Member { .. } => PatternKind::Member(Box::new(Expression { kind, start, end })),
+            ExpressionKind::Array(elements) => {
+                self.tokenizer.require_version("Destructuring", EcmaVersion::Ecma6, start)?;
+                let elements = elements.into_iter()
+                    .map(|element| element.map(|e| self.to_pattern_element(e)).transpose())
+                    .collect::<ParseResult<_>>()?;
+                PatternKind::Array(elements)
+            },
+            ExpressionKind::Object(members) => {
+                self.tokenizer.require_version("Destructuring", EcmaVersion::Ecma6, start)?;
+                let members = members.into_iter().map(|member| match member {
+                    ObjectMember::Property(property) => Ok(ObjectPatternMember::Property(PatternProperty {
+                        key: property.key,
+                        value: self.to_pattern_element(property.value)?,
+                        computed: property.computed,
+                        shorthand: property.shorthand,
+                        start: property.start,
+                        end: property.end
+                    })),
+                    ObjectMember::Spread(spread) => {
+                        // the rest of an object can't be destructured further
+                        let rest = self.to_pattern_element(spread)?;
+                        match rest.kind {
+                            PatternKind::Rest(ref argument) if matches!(argument.kind, PatternKind::Array(_) | PatternKind::Object(_)) => {
+                                Err(self.raise(ParseErrorKind::UnexpectedToken, argument.start))
+                            },
+                            _ => Ok(ObjectPatternMember::Rest(rest))
+                        }
+                    }
+                }).collect::<ParseResult<_>>()?;
+                PatternKind::Object(members)
+            },
+            _ => return Err(self.raise(ParseErrorKind::AssigningToRvalue, start))
+        };
+        Ok(Pattern { kind, start, end })
+    }
+
+    // Array elements and property values may also have a default value
+    // or be the rest element
+    fn to_pattern_element(&self, expr: Expression<'a>) -> ParseResult<Pattern<'a>> {
+        let Expression { kind, start, end } = expr;
+        let kind = match kind {
+            ExpressionKind::Assignment { operator: AssignmentOperator::Assign, left, right } => PatternKind::Assignment { left, right },
+            ExpressionKind::Spread(argument) => PatternKind::Rest(Box::new(self.to_pattern(*argument)?)),
+            kind => return self.to_pattern(Expression { kind, start, end })
+        };
+        Ok(Pattern { kind, start, end })
+    }
+
+    // Parse `yield`, its argument is optional
+    fn parse_yield(&mut self, no_in: bool) -> ParseResult<Expression<'a>> {
+        let start = self.tok.start;
+        self.tokenizer.allow_regexp();
+        self.next()?;
+        let has_argument = !self.is_punc(";") && !self.can_insert_semicolon() &&
+            (self.is_operator("*") || self.starts_expr());
+        let mut delegate = false;
+        let argument = if has_argument {
+            delegate = self.is_operator("*");
+            if delegate { self.next()?; }
+            Some(Box::new(self.parse_maybe_assign(no_in)?))
+        } else {
+            None
+        };
+        Ok(self.expression(ExpressionKind::Yield { argument, delegate }, start))
+    }
+
+    // Parse a ternary conditional (`?:`) operator.
+    fn parse_maybe_conditional(&mut self, no_in: bool) -> ParseResult<Expression<'a>> {
+        let start = self.tok.start;
+        let expr = self.parse_expr_ops(no_in)?;
+        if self.is_bare_arrow(&expr, start) {
+            return Ok(expr)
+        }
+        if self.eat_punc("?")? {
+            let consequent = self.parse_maybe_assign(false)?;
+            self.expect_punc(":")?;
+            let alternate = self.parse_maybe_assign(no_in)?;
+            let kind = ExpressionKind::Conditional {
+                test: Box::new(expr),
+                consequent: Box::new(consequent),
+                alternate: Box::new(alternate)
+            };
+            return Ok(self.expression(kind, start))
+        }
+        Ok(expr)
+    }
+
+    // Start the precedence parser.
+    fn parse_expr_ops(&mut self, no_in: bool) -> ParseResult<Expression<'a>> {
+        let start = self.tok.start;
+        let left = self.parse_maybe_unary()?;
+        if self.is_bare_arrow(&left, start) {
+            return Ok(left)
+        }
+        self.parse_expr_op(left, 0, no_in)
+    }
+
+    // An arrow function starting at `start`, so not parenthesized, ends
+    // the expression, as in `a => {} + b`
+    fn is_bare_arrow(&self, expr: &Expression<'a>, start: usize) -> bool {
+        matches!(expr.kind, ExpressionKind::ArrowFunction(_)) && expr.start == start
+    }
+
+    // Binary operator precedence of the current token, if it is one.
+    // `in` and `instanceof` are relational operators.
+    fn current_binop(&self, no_in: bool) -> Option<u8> {
+        match self.tok.token_type {
+            TokenType::Operator(_) if no_in && self.tok_value() == "in" => None,
+            TokenType::Operator(data) if data.binop != 255 => Some(data.binop),
+            _ => None
+        }
+    }
+
+    fn is_in_operator(&self) -> bool {
+        self.is_operator("in")
+    }
+
+    // Parse binary operators with the operator precedence parsing
+    // algorithm. `left` is the left-hand side of the operator.
+    // `min_prec` provides context that allows the function to stop and
+    // defer further parser to one of its callers when it encounters an
+    // operator that has a lower precedence than the set it is parsing.
+    fn parse_expr_op(&mut self, left: Expression<'a>, min_prec: u8, no_in: bool) -> ParseResult<Expression<'a>> {
+        let prec = match self.current_binop(no_in) {
+            Some(prec) if prec > min_prec => prec,
+            _ => return Ok(left)
+        };
+        let op = self.tok_value().to_string();
+        let logical = op == "||" || op == "&&";
+        let coalesce = op == "??";
+        self.next()?;
+        let operand = self.parse_maybe_unary()?;
+        // '**' is right-associative, the right side of '??' binds like
+        // that of '&&', so that '&&' after it isn't taken in silently
+        let right_prec = match &*op {
+            "**" => prec - 1,
+            "??" => prec + 1,
+            _ => prec
+        };
+        let right = self.parse_expr_op(operand, right_prec, no_in)?;
+        self.check_private_operand(&right)?;
+        // '??' can't be mixed with '||' or '&&' without parentheses
+        if logical && self.is_operator("??") || coalesce && (self.is_operator("||") || self.is_operator("&&")) {
+            return Err(self.raise(ParseErrorKind::MixedCoalesce, self.tok.start))
+        }
+        let start = left.start;
+        let kind = if let Some(operator) = LogicalOperator::from_op(&op) {
+            ExpressionKind::Logical { operator, left: Box::new(left), right: Box::new(right) }
+        } else {
+            let operator = BinaryOperator::from_op(&op)
+                .ok_or_else(|| self.raise(ParseErrorKind::UnexpectedToken, self.last_tok_start))?;
+            ExpressionKind::Binary { operator, left: Box::new(left), right: Box::new(right) }
+        };
+        let expr = self.expression(kind, start);
+        self.parse_expr_op(expr, min_prec, no_in)
+    }
+
+    // Parse unary operators, both prefix and postfix.
+    fn parse_maybe_unary(&mut self) -> ParseResult<Expression<'a>> {
+        if self.is_contextual("await") && self.can_await()? {
+            let expr = self.parse_await()?;
+            self.check_exponent_operand()?;
+            return Ok(expr)
+        }
+        let start = self.tok.start;
+        let is_prefix = match self.tok.token_type {
+            TokenType::Operator(data) => data.prefix,
+            _ => false
+        };
+        if is_prefix {
+            let op = self.tok_value().to_string();
+            self.next()?;
+            let argument = self.parse_maybe_unary()?;
+            self.check_private_operand(&argument)?;
+            if let Some(operator) = UpdateOperator::from_op(&op) {
+                self.check_lvalue(&argument)?;
+                let kind = ExpressionKind::Update { operator, prefix: true, argument: Box::new(argument) };
+                return Ok(self.expression(kind, start))
+            }
+            let operator = UnaryOperator::from_op(&op).ok_or_else(|| self.raise(ParseErrorKind::UnexpectedToken, start))?;
+            if operator == UnaryOperator::Delete && Parser::is_private_member(&argument) {
+                return Err(self.raise(ParseErrorKind::PrivateFieldDelete, start))
+            }
+            self.check_exponent_operand()?;
+            let kind = ExpressionKind::Unary { operator, argument: Box::new(argument) };
+            return Ok(self.expression(kind, start))
+        }
+        // `#a in b` tests whether `b` has the private field
+        if let TokenType::PrivateName = self.tok.token_type {
+            let name = self.parse_private_name()?;
+            if !self.is_in_operator() {
+                return Err(self.unexpected())
+            }
+            return Ok(name)
+        }
+        let mut expr = self.parse_expr_subscripts()?;
+        while let TokenType::Operator(data) = self.tok.token_type {
+            if !data.postfix || self.can_insert_semicolon() { break; }
+            let operator = UpdateOperator::from_op(self.tok_value()).ok_or_else(|| self.unexpected())?;
+            self.check_lvalue(&expr)?;
+            self.next()?;
+            let kind = ExpressionKind::Update { operator, prefix: false, argument: Box::new(expr) };
+            expr = self.expression(kind, start);
+        }
+        Ok(expr)
+    }
+
+    // A private name can only be the left side of `in`, not the right
+    // side of an operator or the operand of a unary one
+    fn check_private_operand(&self, operand: &Expression<'a>) -> ParseResult<()> {
+        if let ExpressionKind::PrivateIdentifier(_) = operand.kind {
+            return Err(self.raise(ParseErrorKind::PrivateNameNotLeft, operand.start))
+        }
+        Ok(())
+    }
+
+    // `a.#b` or `a?.#b`
+    fn is_private_member(expr: &Expression<'a>) -> bool {
+        match expr.kind {
+            ExpressionKind::Member { ref property, .. } => matches!(property.kind, ExpressionKind::PrivateIdentifier(_)),
+            ExpressionKind::Chain(ref expr) => Parser::is_private_member(expr),
+            _ => false
+        }
+    }
+
+    // The left side of '**' can't be a unary or `await` expression, as
+    // in `-x ** 2`, unless it is parenthesized
+    fn check_exponent_operand(&self) -> ParseResult<()> {
+        if self.is_operator("**") {
+            return Err(self.raise(ParseErrorKind::UnaryBeforeExponent, self.tok.start))
+        }
+        Ok(())
+    }
+
+    // `await` is an operator in async functions, and at the top
+    // level of modules
+    fn can_await(&self) -> ParseResult<bool> {
+        if self.in_async {
+            return Ok(true)
+        }
+        if self.in_function || self.in_static_block || !self.tokenizer.options().is_module() {
+            return Ok(false)
+        }
+        self.tokenizer.require_version("Top-level await", EcmaVersion::Ecma2022, self.tok.start)?;
+        Ok(true)
+    }
+
+    fn parse_await(&mut self) -> ParseResult<Expression<'a>> {
+        let start = self.tok.start;
+        self.tokenizer.allow_regexp();
+        self.next()?;
+        let argument = self.parse_maybe_unary()?;
+        self.check_private_operand(&argument)?;
+        Ok(self.expression(ExpressionKind::Await(Box::new(argument)), start))
+    }
+
+    // Parse call, dot, and `[]`-subscript expressions.
+    fn parse_expr_subscripts(&mut self) -> ParseResult<Expression<'a>> {
+        let start = self.tok.start;
+        let base = self.parse_expr_atom()?;
+        // `a => b` can't be called without parentheses
+        if matches!(base.kind, ExpressionKind::ArrowFunction(_)) && &self.tokenizer.input()[self.last_tok_start..self.last_tok_end] != ")" {
+            return Ok(base)
+        }
+        let expr = self.parse_subscripts(base, start, false)?;
+        if let ExpressionKind::Member { .. } = expr.kind {
+            self.pattern_errors.clear_from(start);
+        }
+        Ok(expr)
+    }
+
+    // A `?.` anywhere in the subscripts makes the whole expression
+    // an optional chain.
+    fn parse_subscripts(&mut self, base: Expression<'a>, start: usize, no_calls: bool) -> ParseResult<Expression<'a>> {
+        let mut expr = base;
+        let mut optional_chained = false;
+        loop {
+            let optional = self.is_punc("?.");
+            if optional {
+                if no_calls {
+                    return Err(self.raise(ParseErrorKind::OptionalChainInNew, self.tok.start))
+                }
+                optional_chained = true;
+                self.next()?;
+            }
+            // after `?.` a property name needs no dot
+            if self.eat_punc(".")? || optional && !self.is_punc("[") && !self.is_punc("(") {
+                let property = if matches!(self.tok.token_type, TokenType::PrivateName) && !matches!(expr.kind, ExpressionKind::Super) {
+                    self.parse_private_name()?
+                } else {
+                    let property = self.parse_ident(true)?;
+                    Expression { start: property.start, end: property.end, kind: ExpressionKind::Identifier(property.name) }
+                };
+                let kind = ExpressionKind::Member { object: Box::new(expr), property: Box::new(property), computed: false, optional };
+                expr = self.expression(kind, start);
+            } else if self.eat_punc("[")? {
+                let property = self.parse_expression(false)?;
+                self.expect_punc("]")?;
+                let kind = ExpressionKind::Member { object: Box::new(expr), property: Box::new(property), computed: true, optional };
+                expr = self.expression(kind, start);
+            } else if !no_calls && self.is_punc("(") {
+                let maybe_async_arrow = !optional_chained && self.is_async_arrow_callee(&expr);
+                self.next()?;
+                if maybe_async_arrow {
+                    let (arguments, errors) = self.with_pattern_errors(|p| p.parse_expr_list(")", true))?;
+                    if self.is_punc("=>") && !self.can_insert_semicolon() {
+                        self.tokenizer.require_version("Async function", EcmaVersion::Ecma2017, start)?;
+                        let params = self.to_arrow_params(arguments, errors)?;
+                        let no_in = self.potential_arrow.is_some_and(|(_, no_in)| no_in);
+                        return self.parse_arrow_expression(start, params, true, no_in)
+                    }
+                    self.check_expression_errors(errors)?;
+                    let kind = ExpressionKind::Call { callee: Box::new(expr), arguments, optional };
+                    expr = self.expression(kind, start);
+                    continue;
+                }
+                let arguments = self.parse_expr_list(")", false)?;
+                let kind = ExpressionKind::Call { callee: Box::new(expr), arguments, optional };
+                expr = self.expression(kind, start);
+            } else if self.is_punc("`") {
+                if optional_chained {
+                    return Err(self.raise(ParseErrorKind::OptionalChainInTaggedTemplate, self.tok.start))
+                }
+                let quasi = self.parse_template()?;
+                expr = self.expression(ExpressionKind::TaggedTemplate { tag: Box::new(expr), quasi }, start);
+            } else {
+                break;
+            }
+        }
+        if optional_chained {
+            expr = self.expression(ExpressionKind::Chain(Box::new(expr)), start);
+        }
+        Ok(expr)
+    }
+
+    // `async(` at the start of an assignment expression may begin the
+    // parameters of an async arrow function
+    fn is_async_arrow_callee(&self, callee: &Expression<'a>) -> bool {
+        matches!(callee.kind, ExpressionKind::Identifier(ref name) if name == "async") &&
+            self.potential_arrow.is_some_and(|(start, _)| start == callee.start) &&
+            callee.end == self.last_tok_end && !self.can_insert_semicolon() &&
+            // and isn't written with escapes
+            &self.tokenizer.input()[callee.start..callee.end] == "async"
+    }
+
+    // Parse an atomic expression — either a single token that is an
+    // expression, an expression started by a keyword like `function` or
+    // `new`, or an expression wrapped in punctuation like `()`, `[]`,
+    // or `{}`.
+    fn parse_expr_atom(&mut self) -> ParseResult<Expression<'a>> {
+        let start = self.tok.start;
+        match self.tok.token_type {
+            TokenType::Name if self.is_async_function() => {
+                let function = self.parse_async_function(false, true)?;
+                Ok(self.expression(ExpressionKind::Function(function), start))
+            },
+            TokenType::Name => {
+                self.check_yield_await(self.tok_value(), start)?;
+                self.tokenizer.check_identifier(self.tok_value(), start, self.tokenizer.is_strict(), false)?;
+                let name = self.tok_value().to_string();
+                self.next()?;
+                let is_async = name == "async";
+                let id = self.expression(ExpressionKind::Identifier(name), start);
+                match self.potential_arrow {
+                    Some((arrow_start, no_in)) if arrow_start == start && !self.can_insert_semicolon() => {
+                        self.parse_maybe_name_arrow(id, is_async, no_in)
+                    },
+                    _ => Ok(id)
+                }
+            },
+            TokenType::Num => {
+                let value = self.tok.number.ok_or_else(|| self.raise(ParseErrorKind::InvalidNumber, start))?;
+                self.next()?;
+                Ok(self.expression(ExpressionKind::Literal(Literal::Number(value)), start))
+            },
+            TokenType::BigInt => {
+                let digits = self.tok.bigint.clone().ok_or_else(|| self.unexpected())?;
+                self.next()?;
+                Ok(self.expression(ExpressionKind::Literal(Literal::BigInt(digits)), start))
+            },
+            TokenType::StringLiteral => {
+                let value = self.tok_value().to_string();
+                self.next()?;
+                Ok(self.expression(ExpressionKind::Literal(Literal::String(value)), start))
+            },
+            TokenType::Regexp => {
+                let regex = self.tok.regex.clone().ok_or_else(|| self.unexpected())?;
+                self.next()?;
+                let literal = Literal::Regexp { pattern: regex.pattern.to_string(), flags: regex.flags.to_string() };
+                Ok(self.expression(ExpressionKind::Literal(literal), start))
+            },
+            TokenType::Keyword(_) => {
+                let kind = match self.tok_value() {
+                    "this" => ExpressionKind::This,
+                    "function" => {
+                        self.next()?;
+                        let function = self.parse_function(start, false, true, false)?;
+                        return Ok(self.expression(ExpressionKind::Function(function), start))
+                    },
+                    "class" => {
+                        let class = self.parse_class(false, true)?;
+                        return Ok(self.expression(ExpressionKind::Class(class), start))
+                    },
+                    "super" => return self.parse_super(),
+                    "new" => return self.parse_new(),
+                    "import" => return self.parse_import_meta(),
+                    _ => return Err(self.unexpected())
+                };
+                self.next()?;
+                Ok(self.expression(kind, start))
+            },
+            TokenType::Value(data) => {
+                let literal = match data.atom_value {
+                    Some(value) => Literal::Boolean(value),
+                    None => Literal::Null
+                };
+                self.next()?;
+                Ok(self.expression(ExpressionKind::Literal(literal), start))
+            },
+            TokenType::Punc(data) => match data.punc_type {
+                "(" => self.parse_paren_and_distinguish(),
+                "[" => {
+                    self.next()?;
+                    let elements = self.parse_array_elements()?;
+                    Ok(self.expression(ExpressionKind::Array(elements), start))
+                },
+                "{" => self.parse_object(),
+                "`" => {
+                    let template = self.parse_template()?;
+                    Ok(self.expression(ExpressionKind::Template(template), start))
+                },
+                _ => Err(self.unexpected())
+            },
+            _ => Err(self.unexpected())
+        }
+    }
+
+    // Parse `a => b` or `async a => b` after the name, if an arrow
+    // follows it, otherwise the name is an identifier
+    fn parse_maybe_name_arrow(&mut self, id: Expression<'a>, is_async: bool, no_in: bool) -> ParseResult<Expression<'a>> {
+        let start = id.start;
+        if self.is_punc("=>") {
+            let params = self.to_arrow_params(vec![id], PatternErrors::default())?;
+            return self.parse_arrow_expression(start, params, false, no_in)
+        }
+        if !is_async || !matches!(self.tok.token_type, TokenType::Name) {
+            return Ok(id)
+        }
+        self.tokenizer.require_version("Async function", EcmaVersion::Ecma2017, start)?;
+        if &self.tokenizer.input()[start..id.end] != "async" {
+            return Err(self.raise(ParseErrorKind::EscapedKeyword, start))
+        }
+        let param = self.parse_ident(false)?;
+        if self.can_insert_semicolon() || !self.is_punc("=>") {
+            return Err(self.unexpected())
+        }
+        let param = Expression { kind: ExpressionKind::Identifier(param.name), start: param.start, end: param.end };
+        let params = self.to_arrow_params(vec![param], PatternErrors::default())?;
+        self.parse_arrow_expression(start, params, true, no_in)
+    }
+
+    // Parse a parenthesized expression, or the parameters of an arrow
+    // function, which can't be told apart before the `=>` after them.
+    // Like array and object literals, the contents are parsed as
+    // expressions and converted if they turn out to be parameters.
+    fn parse_paren_and_distinguish(&mut self) -> ParseResult<Expression<'a>> {
+        let start = self.tok.start;
+        let arrow = self.potential_arrow.filter(|&(arrow_start, _)| arrow_start == start);
+        self.next()?;
+        let inner_start = self.tok.start;
+        let mut exprs = Vec::new();
+        // `...a`, only valid in parameters
+        let mut rest = None;
+        let mut trailing_comma = None;
+        let (_, errors) = self.with_pattern_errors(|p| {
+            let mut first = true;
+            while !p.is_punc(")") {
+                if !first {
+                    p.expect_punc(",")?;
+                    if p.is_punc(")") {
+                        trailing_comma = Some(p.last_tok_start);
+                        break;
+                    }
+                } else {
+                    first = false;
+                }
+                if p.is_punc("...") {
+                    rest = Some(p.parse_rest_element()?);
+                    break;
+                }
+                exprs.push(p.parse_maybe_assign_or_pattern(false)?);
+            }
+            Ok(())
+        })?;
+        let inner_end = self.last_tok_end;
+        self.expect_punc(")")?;
+        if let Some((_, no_in)) = arrow {
+            if self.is_punc("=>") && !self.can_insert_semicolon() {
+                if let Some(pos) = trailing_comma {
+                    self.tokenizer.require_version("Trailing comma in parameters", EcmaVersion::Ecma2017, pos)?;
+                }
+                let mut params = self.to_arrow_params(exprs, errors)?;
+                params.extend(rest);
+                return self.parse_arrow_expression(start, params, false, no_in)
+            }
+        }
+        if let Some(rest) = rest {
+            return Err(self.raise(ParseErrorKind::UnexpectedToken, rest.start))
+        }
+        if exprs.is_empty() || trailing_comma.is_some() {
+            return Err(self.raise(ParseErrorKind::UnexpectedToken, self.last_tok_start))
+        }
+        self.check_expression_errors(errors)?;
+        let expr = if exprs.len() > 1 {
+            Expression { kind: ExpressionKind::Sequence(exprs), start: inner_start, end: inner_end }
+        } else {
+            exprs.remove(0)
+        };
+        if !matches!(expr.kind, ExpressionKind::Identifier(_) | ExpressionKind::Member { .. }) {
+            self.pattern_errors.paren_assign = self.pattern_errors.paren_assign.or(Some(start));
+        }
+        self.pattern_errors.paren_bind = self.pattern_errors.paren_bind.or(Some(start));
+        Ok(expr)
+    }
+
+    // Convert the expressions before `=>` to the parameters of an arrow
+    // function, which are bindings rather than assignment targets
+    fn to_arrow_params(&self, exprs: Vec<Expression<'a>>, errors: PatternErrors) -> ParseResult<Vec<Pattern<'a>>> {
+        if let Some(pos) = errors.trailing_comma {
+            return Err(self.raise(ParseErrorKind::CommaAfterRestElement, pos))
+        }
+        if let Some(pos) = errors.paren_bind {
+            return Err(self.raise(ParseErrorKind::ParenthesizedPattern, pos))
+        }
+        let params = exprs.into_iter().map(|expr| self.to_pattern_element(expr)).collect::<ParseResult<Vec<_>>>()?;
+        for param in &params {
+            self.check_binding(param)?;
+        }
+        Ok(params)
+    }
+
+    // Unlike an assignment target, a binding can't be a member expression
+    fn check_binding(&self, pattern: &Pattern<'a>) -> ParseResult<()> {
+        match pattern.kind {
+            PatternKind::Identifier(_) => Ok(()),
+            PatternKind::Member(_) => Err(self.raise(ParseErrorKind::BindingMemberExpression, pattern.start)),
+            PatternKind::Array(ref elements) => elements.iter().flatten().try_for_each(|element| self.check_binding(element)),
+            PatternKind::Object(ref members) => members.iter().try_for_each(|member| match *member {
+                ObjectPatternMember::Property(ref property) => self.check_binding(&property.value),
+                ObjectPatternMember::Rest(ref rest) => self.check_binding(rest)
+            }),
+            PatternKind::Rest(ref argument) => self.check_binding(argument),
+            PatternKind::Assignment { ref left, .. } => self.check_binding(left)
+        }
+    }
+
+    // Parse the body of an arrow function, starting at the `=>`, which
+    // is only a token since ES6. `no_in` is set in the init part of a
+    // `for` loop.
+    fn parse_arrow_expression(&mut self, start: usize, params: Vec<Pattern<'a>>, is_async: bool, no_in: bool) -> ParseResult<Expression<'a>> {
+        if is_async {
+            if let Some(&(_, pos)) = params.iter().flat_map(|p| p.bound_names()).find(|&(name, _)| name == "await").as_ref() {
+                return Err(self.raise(ParseErrorKind::AwaitInAsyncFunction, pos))
+            }
+        }
+        // an arrow function's parameters can never have the same name
+        self.check_duplicate_params(&params)?;
+        self.next()?;
+        // like a function, except that `this`, `super` and `new.target`
+        // are those of the enclosing function
+        let old_in_function = self.in_function;
+        let old_context = (self.in_generator, self.in_async);
+        let old_labels = std::mem::take(&mut self.labels);
+        self.in_function = true;
+        (self.in_generator, self.in_async) = (false, is_async);
+        let body = if self.is_punc("{") {
+            self.parse_function_body(&None, &params, false).map(ArrowBody::Block)
+        } else {
+            self.parse_maybe_assign(no_in).map(|body| ArrowBody::Expression(Box::new(body)))
+        };
+        self.in_function = old_in_function;
+        (self.in_generator, self.in_async) = old_context;
+        self.labels = old_labels;
+        let arrow = ArrowFunction { params, body: body?, is_async, start, end: self.last_tok_end };
+        Ok(self.expression(ExpressionKind::ArrowFunction(arrow), start))
+    }
+
+    // Parse a template literal, starting at its opening '`'. The
+    // tokenizer produces a chunk token before every '${' and before the
+    // closing '`', even when the chunk is empty.
+    fn parse_template(&mut self) -> ParseResult<TemplateLiteral<'a>> {
+        let start = self.tok.start;
+        self.next()?;
+        let mut quasis = Vec::new();
+        let mut expressions = Vec::new();
+        loop {
+            let element = self.parse_template_element()?;
+            let tail = element.tail;
+            quasis.push(element);
+            if tail {
+                break;
+            }
+            self.expect_punc("${")?;
+            expressions.push(self.parse_expression(false)?);
+            self.expect_punc("}")?;
+        }
+        self.expect_punc("`")?;
+        Ok(TemplateLiteral { quasis, expressions, start, end: self.last_tok_end })
+    }
+
+    fn parse_template_element(&mut self) -> ParseResult<TemplateElement> {
+        if !matches!(self.tok.token_type, TokenType::Template) {
+            return Err(self.unexpected())
+        }
+        let start = self.tok.start;
+        let cooked = self.tok_value().to_string();
+        let raw = self.tok.raw.as_deref().unwrap_or("").to_string();
+        self.next()?;
+        Ok(TemplateElement { cooked, raw, tail: self.is_punc("`"), start, end: self.last_tok_end })
+    }
+
+    // New's precedence is slightly tricky. It must allow its argument
+    // to be a `[]` or dot subscript expression, but not a call — at
+    // least, not without wrapping it in parentheses. Thus, it uses the
+    // `no_calls` argument to `parse_subscripts` to prevent it from
+    // consuming the argument list.
+    fn parse_new(&mut self) -> ParseResult<Expression<'a>> {
+        let start = self.tok.start;
+        self.next()?;
+        if self.is_punc(".") {
+            let meta = Identifier { name: "new".to_string(), start, end: self.last_tok_end };
+            self.next()?;
+            let property = self.parse_meta_property("target")?;
+            self.tokenizer.require_version("new.target", EcmaVersion::Ecma6, start)?;
+            if !self.allow_new_target {
+                return Err(self.raise(ParseErrorKind::NewTargetOutsideFunction, start))
+            }
+            return Ok(self.expression(ExpressionKind::MetaProperty { meta, property }, start))
+        }
+        let callee_start = self.tok.start;
+        let callee = self.parse_expr_atom()?;
+        let callee = self.parse_subscripts(callee, callee_start, true)?;
+        let arguments = if self.eat_punc("(")? {
+            self.parse_expr_list(")", false)?
+        } else {
+            Vec::new()
+        };
+        Ok(self.expression(ExpressionKind::New { callee: Box::new(callee), arguments }, start))
+    }
+
+    // `import.meta`, only in modules
+    fn parse_import_meta(&mut self) -> ParseResult<Expression<'a>> {
+        let start = self.tok.start;
+        let meta = Identifier { name: "import".to_string(), start, end: self.tok.end };
+        self.next()?;
+        self.expect_punc(".")?;
+        let property = self.parse_meta_property("meta")?;
+        self.tokenizer.require_version("import.meta", EcmaVersion::Ecma2020, start)?;
+        if !self.tokenizer.options().is_module() {
+            return Err(self.raise(ParseErrorKind::ImportMetaOutsideModule, start))
+        }
+        Ok(self.expression(ExpressionKind::MetaProperty { meta, property }, start))
+    }
+
+    // The `target` or `meta` after the dot of a meta property
+    // `super` is only allowed as the object of a member expression or,
+    // in the constructor of a derived class, as a callee
+    fn parse_super(&mut self) -> ParseResult<Expression<'a>> {
+        let start = self.tok.start;
+        if !self.allow_super {
+            return Err(self.raise(ParseErrorKind::SuperOutsideMethod, start))
+        }
+        self.next()?;
+        if self.is_punc("(") && !self.allow_direct_super {
+            return Err(self.raise(ParseErrorKind::SuperCallOutsideConstructor, start))
+        }
+        if !self.is_punc(".") && !self.is_punc("[") && !self.is_punc("(") {
+            return Err(self.unexpected())
+        }
+        Ok(self.expression(ExpressionKind::Super, start))
+    }
+
+    fn parse_meta_property(&mut self, name: &str) -> ParseResult<Identifier> {
+        let start = self.tok.start;
+        self.expect_contextual(name)?;
+        Ok(Identifier { name: name.to_string(), start, end: self.last_tok_end })
+    }
+
+    fn parse_array_elements(&mut self) -> ParseResult<Vec<Option<Expression<'a>>>> {
+        let mut elements = Vec::new();
+        while !self.eat_punc("]")? {
+            if self.is_punc(",") {
+                self.next()?;
+                elements.push(None);
+            } else {
+                elements.push(Some(self.parse_maybe_spread(true)?));
+                if self.is_punc("]") {
+                    continue;
+                }
+                self.expect_punc(",")?;
+            }
+            if self.is_punc("]") {
+                self.tokenizer.require_version("Trailing comma in array literal", EcmaVersion::Ecma5, self.last_tok_start)?;
+            }
+        }
+        Ok(elements)
+    }
+
+    // Parse an object literal.
+    fn parse_object(&mut self) -> ParseResult<Expression<'a>> {
+        let start = self.tok.start;
+        self.next()?;
+        let mut properties = Vec::new();
+        let mut first = true;
+        while !self.eat_punc("}")? {
+            if !first {
+                self.expect_punc(",")?;
+                if self.is_punc("}") {
+                    self.tokenizer.require_version("Trailing comma in object literal", EcmaVersion::Ecma5, self.last_tok_start)?;
+                    self.next()?;
+                    break;
+                }
+            } else {
+                first = false;
+            }
+            if self.is_punc("...") {
+                self.tokenizer.require_version("Object rest/spread", EcmaVersion::Ecma2018, self.tok.start)?;
+                properties.push(ObjectMember::Spread(self.parse_maybe_spread(true)?));
+            } else {
+                properties.push(ObjectMember::Property(self.parse_property()?));
+            }
+        }
+        Ok(self.expression(ExpressionKind::Object(properties), start))
+    }
+
+    fn parse_property(&mut self) -> ParseResult<Property<'a>> {
+        let start = self.tok.start;
+        let mut is_async = false;
+        // `async` is a modifier if a property name follows it on the
+        // same line, as in `{async a() {}}`
+        if self.is_contextual("async") {
+            let (pos, newline) = self.tokenizer.peek_token_start();
+            if !newline && !self.tokenizer.input()[pos..].starts_with([',', '}', ':', '(', '=']) {
+                self.tokenizer.require_version("Async function", EcmaVersion::Ecma2017, start)?;
+                self.next()?;
+                is_async = true;
+            }
+        }
+        let generator = self.is_operator("*");
+        if generator {
+            self.tokenizer.require_version("Generator function", EcmaVersion::Ecma6, self.tok.start)?;
+            if is_async {
+                self.tokenizer.require_version("Async generator", EcmaVersion::Ecma2018, self.tok.start)?;
+            }
+            self.next()?;
+        }
+        let contextual = self.tok.contextual;
+        let is_name = matches!(self.tok.token_type, TokenType::Name);
+        let (key, computed) = self.parse_property_name()?;
+        if is_async || generator || self.is_punc("(") {
+            return self.parse_property_method(start, key, computed, generator, is_async)
+        }
+        if self.eat_punc(":")? {
+            let value = self.parse_maybe_assign_or_pattern(false)?;
+            return Ok(Property { key, value, kind: PropertyKind::Init, computed, method: false, shorthand: false, start, end: self.last_tok_end })
+        }
+        // `{get}` and `{get = 1}` are shorthand properties
+        let is_accessor = !self.is_punc(",") && !self.is_punc("}") && !self.is_operator("=");
+        let kind = match key.kind {
+            ExpressionKind::Identifier(ref name) if is_accessor && contextual && name == "get" => PropertyKind::Get,
+            ExpressionKind::Identifier(ref name) if is_accessor && contextual && name == "set" => PropertyKind::Set,
+            ExpressionKind::Identifier(ref name) if is_name && !contextual && matches!(&**name, "get" | "set" | "async") && self.is_member_name_start() => {
+                return Err(self.raise(ParseErrorKind::EscapedKeyword, start))
+            },
+            _ => return self.parse_shorthand_property(start, key, is_name && !computed)
+        };
+        self.tokenizer.require_version("Accessor property", EcmaVersion::Ecma5, start)?;
+        let (key, computed) = self.parse_property_name()?;
+        let value = self.parse_method(false, false, false)?;
+        self.check_accessor_params(kind == PropertyKind::Set, &value)?;
+        let function_start = value.start;
+        let value = self.expression(ExpressionKind::Function(value), function_start);
+        Ok(Property { key, value, kind, computed, method: false, shorthand: false, start, end: self.last_tok_end })
+    }
+
+    // Parse the parameters and body of a method like `{a() {}}`, after
+    // its name
+    fn parse_property_method(&mut self, start: usize, key: Expression<'a>, computed: bool, generator: bool, is_async: bool) -> ParseResult<Property<'a>> {
+        if !self.is_punc("(") {
+            return Err(self.unexpected())
+        }
+        self.tokenizer.require_version("Method definition", EcmaVersion::Ecma6, start)?;
+        let value = self.parse_method(generator, is_async, false)?;
+        let function_start = value.start;
+        let value = self.expression(ExpressionKind::Function(value), function_start);
+        Ok(Property { key, value, kind: PropertyKind::Init, computed, method: true, shorthand: false, start, end: self.last_tok_end })
+    }
+
+    // A getter has no parameters and a setter exactly one, which can't
+    // be a rest element
+    fn check_accessor_params(&self, is_setter: bool, function: &Function<'a>) -> ParseResult<()> {
+        let valid = match function.params[..] {
+            [] => !is_setter,
+            [Pattern { kind: PatternKind::Rest(_), .. }] => false,
+            [_] => is_setter,
+            _ => false
+        };
+        if !valid {
+            return Err(self.raise(ParseErrorKind::InvalidAccessorArity, function.start))
+        }
+        Ok(())
+    }
+
+    // Parse the rest of `{a}` or `{a = 1}`, after the name. The second
+    // is only valid if the object turns out to be a pattern.
+    fn parse_shorthand_property(&mut self, start: usize, key: Expression<'a>, is_name: bool) -> ParseResult<Property<'a>> {
+        let name = match key.kind {
+            ExpressionKind::Identifier(ref name) if is_name => name.clone(),
+            _ => return Err(self.unexpected())
+        };
+        self.tokenizer.require_version("Shorthand property", EcmaVersion::Ecma6, start)?;
+        self.check_yield_await(&name, start)?;
+        self.tokenizer.check_identifier(&name, start, self.tokenizer.is_strict(), false)?;
+        let value = if self.is_operator("=") {
+            self.pattern_errors.shorthand_assign = self.pattern_errors.shorthand_assign.or(Some(self.tok.start));
+            self.next()?;
+            let right = self.parse_maybe_assign(false)?;
+            let left = Pattern { kind: PatternKind::Identifier(name), start: key.start, end: key.end };
+            let kind = ExpressionKind::Assignment { operator: AssignmentOperator::Assign, left: Box::new(left), right: Box::new(right) };
+            self.expression(kind, start)
+        } else {
+            key.clone()
+        };
+        Ok(Property { key, value, kind: PropertyKind::Init, computed: false, method: false, shorthand: true, start, end: self.last_tok_end })
+    }
+
+    // Parse a property name, and whether it is computed as in `[a]: b`
+    fn parse_property_name(&mut self) -> ParseResult<(Expression<'a>, bool)> {
+        let start = self.tok.start;
+        match self.tok.token_type {
+            TokenType::Num | TokenType::BigInt | TokenType::StringLiteral => Ok((self.parse_expr_atom()?, false)),
+            TokenType::Punc(data) if data.punc_type == "[" => {
+                self.tokenizer.require_version("Computed property name", EcmaVersion::Ecma6, start)?;
+                self.next()?;
+                let key = self.parse_maybe_assign(false)?;
+                self.expect_punc("]")?;
+                Ok((key, true))
+            },
+            _ => {
+                let ident = self.parse_ident(true)?;
+                Ok((self.expression(ExpressionKind::Identifier(ident.name), start), false))
+            }
+        }
+    }
+
+    // Parse a function declaration or literal (depending on the
+    // `is_statement` parameter), the `function` keyword is already consumed.
+    // `nullable_id` allows a declaration without a name, as in `export default`.
+    fn parse_function(&mut self, start: usize, is_statement: bool, nullable_id: bool, is_async: bool) -> ParseResult<Function<'a>> {
+        let generator = self.is_operator("*");
+        if generator {
+            self.tokenizer.require_version("Generator function", EcmaVersion::Ecma6, self.tok.start)?;
+            if is_async {
+                self.tokenizer.require_version("Async generator", EcmaVersion::Ecma2018, self.tok.start)?;
+            }
+            self.next()?;
+        }
+        // the name of a function expression is bound inside of it
+        let old_context = (self.in_generator, self.in_async);
+        if !is_statement {
+            self.in_generator = generator;
+            self.in_async = is_async;
+        }
+        let id = if let TokenType::Name = self.tok.token_type {
+            self.parse_binding_ident().map(Some)
+        } else if !nullable_id {
+            Err(self.unexpected())
+        } else {
+            Ok(None)
+        };
+        (self.in_generator, self.in_async) = old_context;
+        let id = id?;
+        // `super` refers to the enclosing method's home object, which a
+        // function doesn't have
+        let old_super = (self.allow_super, self.allow_direct_super);
+        (self.allow_super, self.allow_direct_super) = (false, false);
+        let function = self.parse_function_rest(start, id, generator, is_async, is_statement);
+        (self.allow_super, self.allow_direct_super) = old_super;
+        function
+    }
+
+    // Parse the parameters and body of a method, starting at the `(`
+    fn parse_method(&mut self, generator: bool, is_async: bool, allow_direct_super: bool) -> ParseResult<Function<'a>> {
+        let old_super = (self.allow_super, self.allow_direct_super);
+        (self.allow_super, self.allow_direct_super) = (true, allow_direct_super);
+        let start = self.tok.start;
+        let method = self.parse_function_rest(start, None, generator, is_async, false);
+        (self.allow_super, self.allow_direct_super) = old_super;
+        let method = method?;
+        self.check_duplicate_params(&method.params)?;
+        Ok(method)
+    }
+
+    // Parse a class declaration or expression, starting at `class`.
+    // `nullable_id` allows a declaration without a name, as in `export default`.
+    fn parse_class(&mut self, is_statement: bool, nullable_id: bool) -> ParseResult<Class<'a>> {
+        // all parts of a class are strict mode code
+        let old_strict = self.tokenizer.is_strict();
+        self.tokenizer.set_strict(true);
+        let class = self.parse_class_head_and_members(nullable_id);
+        self.tokenizer.set_strict(old_strict);
+        let class = class?;
+        if is_statement {
+            self.expect_punc_before_statement("}")?;
+        } else {
+            self.expect_punc("}")?;
+        }
+        let body = ClassBody { end: self.last_tok_end, ..class.body };
+        Ok(Class { body, end: self.last_tok_end, ..class })
+    }
+
+    // Parse a class up to the `}` closing its body, which is read
+    // outside of strict mode if the class is
+    fn parse_class_head_and_members(&mut self, nullable_id: bool) -> ParseResult<Class<'a>> {
+        let start = self.tok.start;
+        self.next()?;
+        let id = if let TokenType::Name = self.tok.token_type {
+            Some(self.parse_binding_ident()?)
+        } else if !nullable_id {
+            return Err(self.unexpected())
+        } else {
+            None
+        };
+        let super_class = if self.is_keyword("extends") {
+            self.next()?;
+            Some(Box::new(self.parse_expr_subscripts()?))
+        } else {
+            None
+        };
+        let body_start = self.tok.start;
+        self.expect_punc("{")?;
+        self.private_names.push(PrivateNames::default());
+        let members = self.parse_class_members(super_class.is_some());
+        let private_names = self.private_names.pop().unwrap_or_default();
+        let members = members?;
+        self.check_private_names(private_names)?;
+        let body = ClassBody { body: members, start: body_start, end: body_start };
+        Ok(Class { id, super_class, body, start, end: start })
+    }
+
+    fn parse_class_members(&mut self, derived: bool) -> ParseResult<Vec<ClassMember<'a>>> {
+        let mut members = Vec::new();
+        let mut has_constructor = false;
+        while !self.is_punc("}") {
+            if self.eat_punc(";")? {
+                continue;
+            }
+            let member = self.parse_class_member(derived)?;
+            if let ClassMember::Method(MethodDefinition { kind: MethodKind::Constructor, start, .. }) = member {
+                if has_constructor {
+                    return Err(self.raise(ParseErrorKind::DuplicateConstructor, start))
+                }
+                has_constructor = true;
+            }
+            self.declare_private_name(&member)?;
+            members.push(member);
+        }
+        Ok(members)
+    }
+
+    // A private name is declared once per class, except for a getter
+    // and a setter that are both static or both not
+    fn declare_private_name(&mut self, member: &ClassMember<'a>) -> ParseResult<()> {
+        let (key, kind, is_static) = match *member {
+            ClassMember::Method(ref method) => (&method.key, method.kind, method.is_static),
+            ClassMember::Property(ref property) => (&property.key, MethodKind::Method, property.is_static),
+            ClassMember::StaticBlock(_) => return Ok(())
+        };
+        let ExpressionKind::PrivateIdentifier(ref name) = key.kind else {
+            return Ok(())
+        };
+        if name == "constructor" {
+            return Err(self.raise(ParseErrorKind::PrivateNameConstructor, key.start))
+        }
+        let accessor = matches!(kind, MethodKind::Get | MethodKind::Set).then_some((kind, is_static));
+        let Some(scope) = self.private_names.last_mut() else {
+            return Ok(())
+        };
+        match scope.declared.iter_mut().find(|(declared, _)| declared == name) {
+            None => scope.declared.push((name.clone(), accessor)),
+            Some((_, half)) => match (*half, accessor) {
+                (Some((kind, is_static)), Some((other_kind, other_static))) if kind != other_kind && is_static == other_static => {
+                    *half = None;
+                },
+                _ => return Err(self.raise(ParseErrorKind::DuplicatePrivateName, key.start))
+            }
+        }
+        Ok(())
+    }
+
+    // At the end of a class body, names it doesn't declare must be
+    // declared by an enclosing class
+    fn check_private_names(&mut self, private_names: PrivateNames) -> ParseResult<()> {
+        for (name, pos) in private_names.used {
+            if private_names.declared.iter().any(|(declared, _)| *declared == name) {
+                continue;
+            }
+            match self.private_names.last_mut() {
+                Some(scope) => scope.used.push((name, pos)),
+                None => return Err(self.raise(ParseErrorKind::UndeclaredPrivateName, pos))
+            }
+        }
+        Ok(())
+    }
+
+    // Parse `#a`, which is checked against the declarations at the end
+    // of the class body
+    fn parse_private_name(&mut self) -> ParseResult<Expression<'a>> {
+        let start = self.tok.start;
+        let name = self.tok_value().to_string();
+        match self.private_names.last_mut() {
+            Some(scope) => scope.used.push((name.clone(), start)),
+            None => return Err(self.raise(ParseErrorKind::UndeclaredPrivateName, start))
+        }
+        self.next()?;
+        Ok(self.expression(ExpressionKind::PrivateIdentifier(name), start))
+    }
+
+    // Parse `static { ... }`, after `static`. Like a function body it has
+    // its own labels, but `return` and `await` aren't allowed in it.
+    fn parse_static_block(&mut self, start: usize) -> ParseResult<ClassMember<'a>> {
+        self.tokenizer.require_version("Class static block", EcmaVersion::Ecma2022, start)?;
+        let old_context = (self.in_function, self.in_generator, self.in_async, self.in_static_block);
+        let old_super = (self.allow_super, self.allow_direct_super, self.allow_new_target);
+        let old_labels = std::mem::take(&mut self.labels);
+        (self.in_function, self.in_generator, self.in_async, self.in_static_block) = (false, false, false, true);
+        (self.allow_super, self.allow_direct_super, self.allow_new_target) = (true, false, true);
+        let block = self.parse_block();
+        (self.in_function, self.in_generator, self.in_async, self.in_static_block) = old_context;
+        (self.allow_super, self.allow_direct_super, self.allow_new_target) = old_super;
+        self.labels = old_labels;
+        Ok(ClassMember::StaticBlock(Block { start, ..block? }))
+    }
+
+    // Whether the current token can be the name of a class member,
+    // after a modifier like `static`
+    fn is_member_name_start(&self) -> bool {
+        match self.tok.token_type {
+            TokenType::Name | TokenType::PrivateName | TokenType::Num | TokenType::BigInt | TokenType::StringLiteral => true,
+            TokenType::Punc(data) => data.punc_type == "[",
+            _ => self.is_reserved_word()
+        }
+    }
+
+    // Parse a method or field of a class. `derived` is set if the class
+    // extends another, its constructor may call `super()`.
+    fn parse_class_member(&mut self, derived: bool) -> ParseResult<ClassMember<'a>> {
+        let start = self.tok.start;
+        let mut is_static = false;
+        let mut is_async = false;
+        let mut generator = false;
+        let mut kind = MethodKind::Method;
+        // a modifier not followed by a name is the name itself, as in
+        // `static() {}` or `get = 1`
+        let mut modifier_name = None;
+        if self.eat_contextual("static")? {
+            if self.is_punc("{") {
+                return self.parse_static_block(start)
+            }
+            if self.is_member_name_start() || self.is_operator("*") {
+                is_static = true;
+            } else {
+                modifier_name = Some("static");
+            }
+        }
+        if modifier_name.is_none() && self.eat_contextual("async")? {
+            if (self.is_member_name_start() || self.is_operator("*")) && !self.can_insert_semicolon() {
+                self.tokenizer.require_version("Async function", EcmaVersion::Ecma2017, self.last_tok_start)?;
+                is_async = true;
+            } else {
+                modifier_name = Some("async");
+            }
+        }
+        if modifier_name.is_none() && self.is_operator("*") {
+            if is_async {
+                self.tokenizer.require_version("Async generator", EcmaVersion::Ecma2018, self.tok.start)?;
+            }
+            self.next()?;
+            generator = true;
+        }
+        if modifier_name.is_none() && !is_async && !generator {
+            for (name, accessor) in [("get", MethodKind::Get), ("set", MethodKind::Set)] {
+                if self.eat_contextual(name)? {
+                    if self.is_member_name_start() {
+                        kind = accessor;
+                    } else {
+                        modifier_name = Some(name);
+                    }
+                    break;
+                }
+            }
+        }
+        let (key, computed) = match modifier_name {
+            Some(name) => {
+                let key = Expression { kind: ExpressionKind::Identifier(name.to_string()), start: self.last_tok_start, end: self.last_tok_end };
+                (key, false)
+            },
+            None if matches!(self.tok.token_type, TokenType::PrivateName) => (self.parse_private_name()?, false),
+            None => {
+                // `st\u0061tic m() {}`, unlike a field named `static`
+                // followed by a newline
+                let escaped = !self.tok.contextual && ["static", "async", "get", "set"].iter().any(|&name| self.is_word(name));
+                let (key, computed) = self.parse_property_name()?;
+                if escaped && !self.can_insert_semicolon() && (self.is_member_name_start() || self.is_operator("*")) {
+                    return Err(self.raise(ParseErrorKind::EscapedKeyword, key.start))
+                }
+                (key, computed)
+            }
+        };
+        let key_is = |name: &str| !computed && match key.kind {
+            ExpressionKind::Identifier(ref key) | ExpressionKind::Literal(Literal::String(ref key)) => key == name,
+            _ => false
+        };
+        if is_static && key_is("prototype") {
+            return Err(self.raise(ParseErrorKind::StaticPrototype, key.start))
+        }
+        if self.is_punc("(") || kind != MethodKind::Method || generator || is_async {
+            let is_constructor = !is_static && key_is("constructor");
+            if is_constructor {
+                let reason = match kind {
+                    MethodKind::Get | MethodKind::Set => Some("have get/set modifier"),
+                    _ if generator => Some("be a generator"),
+                    _ if is_async => Some("be an async method"),
+                    _ => None
+                };
+                if let Some(reason) = reason {
+                    return Err(self.raise(ParseErrorKind::InvalidConstructor(reason), key.start))
+                }
+                kind = MethodKind::Constructor;
+            }
+            let value = self.parse_method(generator, is_async, is_constructor && derived)?;
+            if let MethodKind::Get | MethodKind::Set = kind {
+                self.check_accessor_params(kind == MethodKind::Set, &value)?;
+            }
+            let method = MethodDefinition { key, value, kind, computed, is_static, start, end: self.last_tok_end };
+            return Ok(ClassMember::Method(method))
+        }
+        self.tokenizer.require_version("Class field", EcmaVersion::Ecma2022, start)?;
+        if key_is("constructor") {
+            return Err(self.raise(ParseErrorKind::FieldNamedConstructor, key.start))
+        }
+        let value = if self.is_operator("=") {
+            self.next()?;
+            // the initializer is evaluated like the body of a method
+            let old_context = (self.in_generator, self.in_async, self.allow_super, self.allow_direct_super);
+            (self.in_generator, self.in_async, self.allow_super, self.allow_direct_super) = (false, false, true, false);
+            let value = self.parse_maybe_assign(false);
+            (self.in_generator, self.in_async, self.allow_super, self.allow_direct_super) = old_context;
+            Some(value?)
+        } else {
+            None
+        };
+        self.semicolon()?;
+        Ok(ClassMember::Property(PropertyDefinition { key, value, computed, is_static, start, end: self.last_tok_end }))
+    }
+
+    // Parse `async function`, starting at `async`
+    fn parse_async_function(&mut self, is_statement: bool, nullable_id: bool) -> ParseResult<Function<'a>> {
+        let start = self.tok.start;
+        self.tokenizer.require_version("Async function", EcmaVersion::Ecma2017, start)?;
+        self.check_unescaped()?;
+        self.next()?;
+        self.next()?;
+        self.parse_function(start, is_statement, nullable_id, true)
+    }
+
+    // Parse the parameter list and body of a function.
+    fn parse_function_rest(&mut self, start: usize, id: Option<Identifier>, generator: bool, is_async: bool, is_statement: bool) -> ParseResult<Function<'a>> {
+        // Start a new scope with regard to labels, the `in_function`
+        // flag and the `yield` and `await` operators (restore them to
+        // their old value afterwards). Parameters are in the new scope.
+        let old_in_function = (self.in_function, self.allow_new_target, self.in_static_block);
+        let old_context = (self.in_generator, self.in_async);
+        let old_labels = std::mem::take(&mut self.labels);
+        (self.in_function, self.allow_new_target, self.in_static_block) = (true, true, false);
+        self.in_generator = generator;
+        self.in_async = is_async;
+        let params_and_body = self.parse_params_and_body(&id, is_statement);
+        (self.in_function, self.allow_new_target, self.in_static_block) = old_in_function;
+        (self.in_generator, self.in_async) = old_context;
+        self.labels = old_labels;
+        let (params, body) = params_and_body?;
+        Ok(Function { id, params, body, generator, is_async, start, end: self.last_tok_end })
+    }
+
+    fn parse_params_and_body(&mut self, id: &Option<Identifier>, is_statement: bool) -> ParseResult<(Vec<Pattern<'a>>, Block<'a>)> {
+        self.expect_punc("(")?;
+        let mut params = Vec::new();
+        let mut first = true;
+        while !self.eat_punc(")")? {
+            if !first {
+                self.expect_punc(",")?;
+            } else {
+                first = false;
+            }
+            if self.is_punc("...") {
+                params.push(self.parse_rest_element()?);
+                self.expect_punc(")")?;
+                break;
+            }
+            params.push(self.parse_binding_element()?);
+        }
+        let body = self.parse_function_body(id, &params, is_statement)?;
+        Ok((params, body))
+    }
+
+    fn parse_function_body(&mut self, id: &Option<Identifier>, params: &[Pattern<'a>], is_statement: bool) -> ParseResult<Block<'a>> {
+        let start = self.tok.start;
+        self.expect_punc("{")?;
+        let old_strict = self.tokenizer.is_strict();
+        let body = self.parse_directives_and_statements(false, true);
+        // a "use strict" directive applies to the name and
+        // parameters too, so they are checked after the body
+        let checked = match body {
+            Ok(_) if self.tokenizer.is_strict() => self.check_function_names(id, params),
+            _ => Ok(())
+        };
+        self.tokenizer.set_strict(old_strict);
+        let body = body?;
+        checked?;
+        if is_statement {
+            self.expect_punc_before_statement("}")?;
+        } else {
+            self.expect_punc("}")?;
+        }
+        Ok(Block { body, start, end: self.last_tok_end })
+    }
+
+    fn check_function_names(&self, id: &Option<Identifier>, params: &[Pattern<'a>]) -> ParseResult<()> {
+        if let Some(ref ident) = *id {
+            self.tokenizer.check_identifier(&ident.name, ident.start, true, true)?;
+        }
+        for (name, pos) in params.iter().flat_map(|p| p.bound_names()) {
+            self.tokenizer.check_identifier(name, pos, true, true)?;
+        }
+        self.check_duplicate_params(params)
+    }
+
+    // The parameters of strict functions, arrow functions and methods
+    // can't bind a name twice
+    fn check_duplicate_params(&self, params: &[Pattern<'a>]) -> ParseResult<()> {
+        let names: Vec<_> = params.iter().flat_map(|p| p.bound_names()).collect();
+        for (i, &(name, pos)) in names.iter().enumerate() {
+            if names[..i].iter().any(|&(other, _)| other == name) {
+                return Err(self.raise(ParseErrorKind::DuplicateParameter, pos))
+            }
+        }
+        Ok(())
+    }
+
+    // Parses a comma-separated list of expressions, and returns them as
+    // a vector. `close` is the punctuation that ends the list.
+    // `maybe_params` is set for the arguments of `async(...)`, which may
+    // turn out to be the parameters of an arrow function.
+    fn parse_expr_list(&mut self, close: &str, maybe_params: bool) -> ParseResult<Vec<Expression<'a>>> {
+        let mut elements = Vec::new();
+        let mut first = true;
+        while !self.eat_punc(close)? {
+            if !first {
+                self.expect_punc(",")?;
+            } else {
+                first = false;
+            }
+            elements.push(self.parse_maybe_spread(maybe_params)?);
+        }
+        Ok(elements)
+    }
+
+    // Parse an array element, argument or object member, which may be
+    // spread with `...`. `in_literal` is set for the elements of array
+    // and object literals, which may turn out to be patterns, and for
+    // arguments that may turn out to be parameters.
+    fn parse_maybe_spread(&mut self, in_literal: bool) -> ParseResult<Expression<'a>> {
+        let start = self.tok.start;
+        let spread = self.eat_punc("...")?;
+        let expr = if in_literal { self.parse_maybe_assign_or_pattern(false)? } else { self.parse_maybe_assign(false)? };
+        if !spread {
+            return Ok(expr)
+        }
+        if in_literal && self.is_punc(",") {
+            self.pattern_errors.trailing_comma = self.pattern_errors.trailing_comma.or(Some(self.tok.start));
+        }
+        Ok(self.expression(ExpressionKind::Spread(Box::new(expr)), start))
+    }
+
+    // Parse the parenthesized header of `if`, `while`, `do`/`while`,
+    // `switch` or `with`
+    fn parse_paren_expression(&mut self) -> ParseResult<Expression<'a>> {
+        self.expect_punc("(")?;
+        let expr = self.parse_expression(false)?;
+        self.expect_punc_before_statement(")")?;
+        Ok(expr)
+    }
+
+    // Parse the next token as an identifier. If `liberal` is true (used
+    // when parsing properties), it will also convert keywords into
+    // identifiers.
+    fn parse_ident(&mut self, liberal: bool) -> ParseResult<Identifier> {
+        let is_ident = match self.tok.token_type {
+            TokenType::Name => true,
+            _ => liberal && self.is_reserved_word()
+        };
+        if !is_ident {
+            return Err(self.unexpected())
+        }
+        // ES3 doesn't allow reserved words anywhere
+        if liberal && !self.tokenizer.options().at_least(EcmaVersion::Ecma5) &&
+            (self.is_reserved_word() || self.tokenizer.check_identifier(self.tok_value(), self.tok.start, false, false).is_err()) {
+            let kind = ParseErrorKind::FeatureRequiresVersion { feature: "Reserved word as property name", version: EcmaVersion::Ecma5 };
+            return Err(self.raise(kind, self.tok.start))
+        }
+        if !liberal {
+            self.check_yield_await(self.tok_value(), self.tok.start)?;
+            self.tokenizer.check_identifier(self.tok_value(), self.tok.start, self.tokenizer.is_strict(), false)?;
+        }
+        let ident = Identifier { name: self.tok_value().to_string(), start: self.tok.start, end: self.tok.end };
+        self.next()?;
+        Ok(ident)
+    }
+
+    // Parse an identifier that is declared
+    fn parse_binding_ident(&mut self) -> ParseResult<Identifier> {
+        let ident = self.parse_ident(false)?;
+        self.tokenizer.check_identifier(&ident.name, ident.start, self.tokenizer.is_strict(), true)?;
+        Ok(ident)
+    }
+
+    // Parse a declared name or, since ES6, an array or object pattern
+    fn parse_binding_atom(&mut self) -> ParseResult<Pattern<'a>> {
+        let start = self.tok.start;
+        if self.is_punc("[") {
+            self.tokenizer.require_version("Destructuring", EcmaVersion::Ecma6, start)?;
+            self.next()?;
+            let elements = self.parse_binding_elements()?;
+            return Ok(Pattern { kind: PatternKind::Array(elements), start, end: self.last_tok_end })
+        }
+        if self.is_punc("{") {
+            self.tokenizer.require_version("Destructuring", EcmaVersion::Ecma6, start)?;
+            return self.parse_object_pattern()
+        }
+        let ident = self.parse_binding_ident()?;
+        Ok(Pattern { kind: PatternKind::Identifier(ident.name), start, end: ident.end })
+    }
+
+    // Parse the elements of an array pattern, after the `[`
+    fn parse_binding_elements(&mut self) -> ParseResult<Vec<Option<Pattern<'a>>>> {
+        let mut elements = Vec::new();
+        let mut first = true;
+        while !self.eat_punc("]")? {
+            if !first {
+                self.expect_punc(",")?;
+                if self.eat_punc("]")? { break; }
+            } else {
+                first = false;
+            }
+            if self.is_punc(",") {
+                elements.push(None);
+            } else if self.is_punc("...") {
+                elements.push(Some(self.parse_rest_element()?));
+                self.expect_punc("]")?;
+                break;
+            } else {
+                elements.push(Some(self.parse_binding_element()?));
+            }
+        }
+        Ok(elements)
+    }
+
+    // Parse an object pattern, starting at the `{`
+    fn parse_object_pattern(&mut self) -> ParseResult<Pattern<'a>> {
+        let start = self.tok.start;
+        self.next()?;
+        let mut members = Vec::new();
+        let mut first = true;
+        while !self.eat_punc("}")? {
+            if !first {
+                self.expect_punc(",")?;
+                if self.eat_punc("}")? { break; }
+            } else {
+                first = false;
+            }
+            if self.is_punc("...") {
+                self.tokenizer.require_version("Object rest/spread", EcmaVersion::Ecma2018, self.tok.start)?;
+                // the rest of an object can't be destructured further
+                let rest_start = self.tok.start;
+                self.next()?;
+                let ident = self.parse_binding_ident()?;
+                if self.is_punc(",") {
+                    return Err(self.raise(ParseErrorKind::CommaAfterRestElement, self.tok.start))
+                }
+                let argument = Pattern { kind: PatternKind::Identifier(ident.name), start: ident.start, end: ident.end };
+                let rest = Pattern { kind: PatternKind::Rest(Box::new(argument)), start: rest_start, end: self.last_tok_end };
+                members.push(ObjectPatternMember::Rest(rest));
+                continue;
+            }
+            let property_start = self.tok.start;
+            let is_name = matches!(self.tok.token_type, TokenType::Name);
+            let (key, computed) = self.parse_property_name()?;
+            let (value, shorthand) = if self.eat_punc(":")? {
+                (self.parse_binding_element()?, false)
+            } else {
+                let name = match key.kind {
+                    ExpressionKind::Identifier(ref name) if is_name && !computed => name.clone(),
+                    _ => return Err(self.unexpected())
+                };
+                self.check_yield_await(&name, key.start)?;
+                self.tokenizer.check_identifier(&name, key.start, self.tokenizer.is_strict(), true)?;
+                let target = Pattern { kind: PatternKind::Identifier(name), start: key.start, end: key.end };
+                (self.parse_maybe_default(property_start, target)?, true)
+            };
+            let property = PatternProperty { key, value, computed, shorthand, start: property_start, end: self.last_tok_end };
+            members.push(ObjectPatternMember::Property(property));
+        }
+        Ok(Pattern { kind: PatternKind::Object(members), start, end: self.last_tok_end })
+    }
+
+    // Parse a binding target and its default value, if any
+    fn parse_binding_element(&mut self) -> ParseResult<Pattern<'a>> {
+        let start = self.tok.start;
+        let target = self.parse_binding_atom()?;
+        self.parse_maybe_default(start, target)
+    }
+
+    fn parse_maybe_default(&mut self, start: usize, target: Pattern<'a>) -> ParseResult<Pattern<'a>> {
+        if !self.is_operator("=") {
+            return Ok(target)
+        }
+        self.tokenizer.require_version("Default value", EcmaVersion::Ecma6, self.tok.start)?;
+        self.next()?;
+        let right = self.parse_maybe_assign(false)?;
+        let kind = PatternKind::Assignment { left: Box::new(target), right: Box::new(right) };
+        Ok(Pattern { kind, start, end: self.last_tok_end })
+    }
+
+    // Parse `...` and its target, which must be the last element
+    fn parse_rest_element(&mut self) -> ParseResult<Pattern<'a>> {
+        let start = self.tok.start;
+        self.next()?;
+        let argument = self.parse_binding_atom()?;
+        if self.is_punc(",") {
+            return Err(self.raise(ParseErrorKind::CommaAfterRestElement, self.tok.start))
+        }
+        Ok(Pattern { kind: PatternKind::Rest(Box::new(argument)), start, end: self.last_tok_end })
+    }
+
+    // `yield` is reserved in generators, `await` in async
+    // functions, modules and class static blocks, which also reserve
+    // `arguments`
+    fn check_yield_await(&self, name: &str, pos: usize) -> ParseResult<()> {
+        let kind = match name {
+            "await" if self.in_static_block => ParseErrorKind::InvalidInStaticBlock("await"),
+            "arguments" if self.in_static_block => ParseErrorKind::InvalidInStaticBlock("arguments"),
+            "yield" if self.in_generator => ParseErrorKind::YieldInGenerator,
+            "await" if self.in_async => ParseErrorKind::AwaitInAsyncFunction,
+            "await" if self.tokenizer.options().is_module() => ParseErrorKind::AwaitInModule,
+            _ => return Ok(())
+        };
+        Err(self.raise(kind, pos))
+    }
+
+    // Verify that a node is an lval — something that can be assigned
+    // to.
+    fn check_lvalue(&self, expr: &Expression<'a>) -> ParseResult<()> {
+        match expr.kind {
+            ExpressionKind::Identifier(ref name) => {
+                self.tokenizer.check_identifier(name, expr.start, self.tokenizer.is_strict(), true)
+            },
+            ExpressionKind::Member { .. } => Ok(()),
+            _ => Err(self.raise(ParseErrorKind::AssigningToRvalue, expr.start))
+        }
+    }
+}
@@ -0,0 +1,1861 @@
+use std::fmt;
+use std::char;
+use std::borrow::Cow;
+use std::iter::FusedIterator;
+
+use crate::location::{LineIndex, SourceLocation};
+use crate::unicode;
+
+static STRICT_MODE_BAD_WORDS: [&str; 2] = ["eval", "arguments"];
+static ECMA3_RESERVED_WORDS: [&str; 31] = ["abstract", "boolean", "byte", "char", "class", "const", "debugger", "double", "enum", "export", "extends", "final", "float", "goto", "implements", "import", "int", "interface", "long", "native", "package", "private", "protected", "public", "short", "static", "super", "synchronized", "throws", "transient", "volatile"];
+static ECMA5_RESERVED_WORDS: [&str; 7] = ["class", "enum", "extends", "super", "const", "export", "import"];
+// the other reserved words of ES6 are keywords
+static ECMA6_RESERVED_WORDS: [&str; 2] = ["enum", "super"];
+static STRICT_MODE_RESERVED_WORDS: [&str; 9] = ["implements", "interface", "let", "package", "private", "protected", "public", "static", "yield"];
+// `debugger` is only reserved in ES3
+static ECMA3_KEYWORDS: [&str; 28] = ["break", "case", "catch", "continue", "default", "do", "else", "finally", "for", "function", "if", "return", "switch", "throw", "try", "var", "while", "with", "null", "true", "false", "instanceof", "typeof", "void", "delete", "new", "in", "this"];
+static ECMA5_KEYWORDS: [&str; 29] = ["break", "case", "catch", "continue", "debugger", "default", "do", "else", "finally", "for", "function", "if", "return", "switch", "throw", "try", "var", "while", "with", "null", "true", "false", "instanceof", "typeof", "void", "delete", "new", "in", "this"];
+static ECMA6_KEYWORDS: [&str; 35] = ["break", "case", "catch", "continue", "debugger", "default", "do", "else", "finally", "for", "function", "if", "return", "switch", "throw", "try", "var", "while", "with", "null", "true", "false", "instanceof", "typeof", "void", "delete", "new", "in", "this", "const", "class", "extends", "export", "import", "super"];
+// names that are only keywords in some contexts, the parser decides
+static CONTEXTUAL_KEYWORDS: [&str; 12] = ["as", "async", "await", "from", "get", "let", "meta", "of", "set", "static", "target", "yield"];
+
+// keyword data
+static BREAK: KeywordData = KeywordData { keyword: "break", is_loop: false, before_expr: false };
+static CASE: KeywordData = KeywordData { keyword: "case", is_loop: false, before_expr: true };
+static CATCH: KeywordData = KeywordData { keyword: "catch", is_loop: false, before_expr: false };
+static CLASS: KeywordData = KeywordData { keyword: "class", is_loop: false, before_expr: false };
+static CONST: KeywordData = KeywordData { keyword: "const", is_loop: false, before_expr: false };
+static CONTINUE: KeywordData = KeywordData { keyword: "continue", is_loop: false, before_expr: false };
+static DEBUGGER: KeywordData = KeywordData { keyword: "debugger", is_loop: false, before_expr: false };
+static DEFAULT: KeywordData = KeywordData { keyword: "default", is_loop: false, before_expr: false };
+static DO: KeywordData = KeywordData { keyword: "do", is_loop: true, before_expr: false };
+static ELSE: KeywordData = KeywordData { keyword: "else", is_loop: false, before_expr: true };
+static EXPORT: KeywordData = KeywordData { keyword: "export", is_loop: false, before_expr: false };
+static EXTENDS: KeywordData = KeywordData { keyword: "extends", is_loop: false, before_expr: true };
+static FINALLY: KeywordData = KeywordData { keyword: "finally", is_loop: false, before_expr: false };
+static IMPORT: KeywordData = KeywordData { keyword: "import", is_loop: false, before_expr: false };
+static FOR: KeywordData = KeywordData { keyword: "for", is_loop: true, before_expr: false };
+static FUNCTION: KeywordData = KeywordData { keyword: "function", is_loop: false, before_expr: false };
+static IF: KeywordData = KeywordData { keyword: "if", is_loop: false, before_expr: false };
+static NEW: KeywordData = KeywordData { keyword: "new", is_loop: false, before_expr: true };
+static RETURN: KeywordData = KeywordData { keyword: "return", is_loop: false, before_expr: true };
+static SUPER: KeywordData = KeywordData { keyword: "super", is_loop: false, before_expr: false };
+static SWITCH: KeywordData = KeywordData { keyword: "switch", is_loop: false, before_expr: false };
+static THIS: KeywordData = KeywordData { keyword: "this", is_loop: false, before_expr: false };
+static THROW: KeywordData = KeywordData { keyword: "throw", is_loop: false, before_expr: true };
+static TRY: KeywordData = KeywordData { keyword: "try", is_loop: false, before_expr: false };
+static VAR: KeywordData = KeywordData { keyword: "var", is_loop: false, before_expr: false };
+static WHILE: KeywordData = KeywordData { keyword: "while", is_loop: true, before_expr: false };
+static WITH: KeywordData = KeywordData { keyword: "with", is_loop: false, before_expr: false };
+
+// values
+static NULL: ValueData = ValueData { keyword: "null", atom_value: None };
+static TRUE: ValueData = ValueData { keyword: "true", atom_value: Some(true) };
+static FALSE: ValueData = ValueData { keyword: "false", atom_value: Some(false) };
+
+// punc data
+static ARROW: PuncData = PuncData { punc_type: "=>", before_expr: true };
+static BQUOTE: PuncData = PuncData { punc_type: "`", before_expr: false };
+static BRACKET_L: PuncData = PuncData { punc_type: "[", before_expr: true };
+static BRACKET_R: PuncData = PuncData { punc_type: "]", before_expr: false };
+static BRACE_L: PuncData = PuncData { punc_type: "{", before_expr: true };
+static BRACE_R: PuncData = PuncData { punc_type: "}", before_expr: false };
+static COLON: PuncData = PuncData { punc_type: ":", before_expr: true };
+static COMMA: PuncData = PuncData { punc_type: ",", before_expr: true };
+static DOLLAR_BRACE_L: PuncData = PuncData { punc_type: "${", before_expr: true };
+static DOT: PuncData = PuncData { punc_type: ".", before_expr: false };
+static ELLIPSIS: PuncData = PuncData { punc_type: "...", before_expr: true };
+static PAREN_L: PuncData = PuncData { punc_type: "(", before_expr: true };
+static PAREN_R: PuncData = PuncData { punc_type: ")", before_expr: false };
+static QUESTION: PuncData = PuncData { punc_type: "?", before_expr: true };
+static QUESTION_DOT: PuncData = PuncData { punc_type: "?.", before_expr: false };
+static SEMI: PuncData = PuncData { punc_type: ";", before_expr: true };
+
+// operators
+static SLASH: OperatorData = OperatorData { binop: 10, before_expr: true, is_assign: false, is_update: false, postfix: false, prefix: false };
+static EQ: OperatorData = OperatorData { binop: 255, before_expr: true, is_assign: true, is_update: false, postfix: false, prefix: false };
+static ASSIGN: OperatorData = OperatorData { binop: 255, before_expr: true, is_assign: true, is_update: false, postfix: false, prefix: false };
+static INC_DEC: OperatorData = OperatorData { binop: 255, before_expr: false, is_assign: false, is_update: true, postfix: true, prefix: true };
+static PREFIX: OperatorData = OperatorData { binop: 255, before_expr: true, is_assign: false, is_update: false, postfix: false, prefix: true };
+static NULLISH_COALESCING: OperatorData = OperatorData { binop: 1, before_expr: true, is_assign: false, is_update: false, postfix: false, prefix: false };
+static LOGICAL_OR: OperatorData = OperatorData { binop: 1, before_expr: true, is_assign: false, is_update: false, postfix: false, prefix: false };
+static LOGICIAL_AND: OperatorData = OperatorData { binop: 2, before_expr: true, is_assign: false, is_update: false, postfix: false, prefix: false };
+static BITWISE_OR: OperatorData = OperatorData { binop: 3, before_expr: true, is_assign: false, is_update: false, postfix: false, prefix: false };
+static BITWISE_XOR: OperatorData = OperatorData { binop: 4, before_expr: true, is_assign: false, is_update: false, postfix: false, prefix: false };
+static BITWISE_AND: OperatorData = OperatorData { binop: 5, before_expr: true, is_assign: false, is_update: false, postfix: false, prefix: false };
+static EQUALITY: OperatorData = OperatorData { binop: 6, before_expr: true, is_assign: false, is_update: false, postfix: false, prefix: false };
+static RELATIONAL: OperatorData = OperatorData { binop: 7, before_expr: true, is_assign: false, is_update: false, postfix: false, prefix: false };
+static BIT_SHIFT: OperatorData = OperatorData { binop: 8, before_expr: true, is_assign: false, is_update: false, postfix: false, prefix: false };
+static PLUS_MIN: OperatorData = OperatorData { binop: 9, before_expr: true, is_assign: false, is_update: false, postfix: false, prefix: true };
+static MODULO: OperatorData = OperatorData { binop: 10, before_expr: true, is_assign: false, is_update: false, postfix: false, prefix: false };
+// '*' may be multiply or have special meaning in ES6
+static STAR: OperatorData = OperatorData { binop: 10, before_expr: true, is_assign: false, is_update: false, postfix: false, prefix: false };
+// '**' is right-associative
+static EXPONENT: OperatorData = OperatorData { binop: 11, before_expr: true, is_assign: false, is_update: false, postfix: false, prefix: false };
+
+
+pub struct Options {
+    pub version: EcmaVersion,
+    pub source_type: SourceType,
+    // collect comments, see `Tokenizer::comments`
+    pub collect_comments: bool,
+    // recover from errors instead of stopping at the first,
+    // see `Tokenizer::errors`
+    pub tolerant: bool
+}
+
+impl Default for Options {
+    fn default() -> Options {
+        Options { version: EcmaVersion::Ecma2024, source_type: SourceType::Script, collect_comments: false, tolerant: false }
+    }
+}
+
+impl Options {
+    pub fn is_ecma6(&self) -> bool {
+        self.at_least(EcmaVersion::Ecma6)
+    }
+
+    pub fn at_least(&self, version: EcmaVersion) -> bool {
+        self.version >= version
+    }
+
+    pub fn is_module(&self) -> bool {
+        matches!(self.source_type, SourceType::Module)
+    }
+}
+
+// Modules don't allow the HTML-like comments of Annex B
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SourceType {
+    Script,
+    Module
+}
+
+// Versions are ordered, `Ecma6` is ES2015
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum EcmaVersion {
+    Ecma3,
+    Ecma5,
+    Ecma6,
+    Ecma2016,
+    Ecma2017,
+    Ecma2018,
+    Ecma2019,
+    Ecma2020,
+    Ecma2021,
+    Ecma2022,
+    Ecma2023,
+    Ecma2024
+}
+
+impl EcmaVersion {
+    pub fn name(&self) -> &'static str {
+        match *self {
+            EcmaVersion::Ecma3 => "ES3",
+            EcmaVersion::Ecma5 => "ES5",
+            EcmaVersion::Ecma6 => "ES2015",
+            EcmaVersion::Ecma2016 => "ES2016",
+            EcmaVersion::Ecma2017 => "ES2017",
+            EcmaVersion::Ecma2018 => "ES2018",
+            EcmaVersion::Ecma2019 => "ES2019",
+            EcmaVersion::Ecma2020 => "ES2020",
+            EcmaVersion::Ecma2021 => "ES2021",
+            EcmaVersion::Ecma2022 => "ES2022",
+            EcmaVersion::Ecma2023 => "ES2023",
+            EcmaVersion::Ecma2024 => "ES2024"
+        }
+    }
+}
+
+// Tokens borrow from the source, only the values of strings, template
+// chunks and identifiers with escapes are allocated
+#[derive(Debug)]
+pub struct Token<'a> {
+    pub value: Option<Cow<'a, str>>,
+    pub token_type: TokenType,
+    // the source text of the token
+    pub source: &'a str,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+    // pattern and flags of a regular expression literal
+    pub regex: Option<RegexpValue<'a>>,
+    // raw source of a template chunk, `value` holds the cooked string,
+    // or of a number
+    pub raw: Option<Cow<'a, str>>,
+    // value of a number
+    pub number: Option<f64>,
+    // decimal digits of a BigInt, without the 'n', only allocated if
+    // they are written in another radix or with separators
+    pub bigint: Option<Cow<'a, str>>,
+    // set on names like `let` or `async` that are keywords in some
+    // contexts, unless they contain escapes
+    pub contextual: bool
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RegexpValue<'a> {
+    pub pattern: &'a str,
+    pub flags: &'a str
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comment<'a> {
+    pub kind: CommentKind,
+    // text without the comment delimiters
+    pub text: &'a str,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CommentKind {
+    Line,
+    Block,
+    // `#!` line at the very start of the input
+    Hashbang,
+    // `<!--` and `-->` comments of Annex B, up to the end of the line
+    Html
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TokenType {
+    StringLiteral,
+    Name,
+    Num,
+    BigInt,
+    Regexp,
+    Template,
+    // `#a` in a class, the value is the name without the `#`
+    PrivateName,
+    Keyword(KeywordData),
+    Punc(PuncData),
+    Value(ValueData),
+    Operator(OperatorData),
+    // source that failed to tokenize in tolerant mode
+    Invalid,
+    Eof
+}
+
+impl TokenType {
+    /// whether an expression may follow a token of this type,
+    /// used to tell a regular expression from a division
+    pub fn before_expr(&self) -> bool {
+        match *self {
+            TokenType::Keyword(data) => data.before_expr,
+            TokenType::Punc(data) => data.before_expr,
+            TokenType::Operator(data) => data.before_expr,
+            _ => false
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KeywordData {
+    pub keyword: &'static str,
+    pub is_loop: bool,
+    pub before_expr: bool
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ValueData {
+    pub keyword: &'static str,
+    pub atom_value: Option<bool>
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PuncData {
+    pub punc_type: &'static str,
+    pub before_expr: bool
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OperatorData {
+    pub binop: u8,
+    pub before_expr: bool,
+    pub is_assign: bool,
+    pub postfix: bool,
+    pub prefix: bool,
+    pub is_update: bool
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ParseErrorKind {
+    // tokenizer errors
+    ExpectedUnicodeEscape,
+    IdentifierDirectlyAfterNumber,
+    InvalidNumber,
+    InvalidRegexpFlag,
+    InvalidValue,
+    InvalidUnicodeEscape,
+    BadEscapeSequence,
+    CodePointOutOfBounds,
+    OctalInStrictMode,
+    LeadingZeroInStrictMode,
+    MisplacedNumericSeparator,
+    InvalidBigInt,
+    UnexpectedCharacter,
+    UnterminatedComment,
+    UnterminatedRegexp,
+    UnterminatedStringConstant,
+    UnterminatedTemplate,
+    OctalInTemplate,
+
+    // parser errors
+    UnexpectedToken,
+    AssigningToRvalue,
+    IllegalNewlineAfterThrow,
+    InvalidAccessorArity,
+    LabelAlreadyDeclared,
+    MissingCatchOrFinally,
+    MultipleDefaultClauses,
+    ReturnOutsideFunction,
+    UnsyntacticBreak,
+    UnsyntacticContinue,
+    WithInStrictMode,
+    ImportExportOutsideModule,
+    ImportExportNotTopLevel,
+    AwaitInModule,
+    YieldInGenerator,
+    AwaitInAsyncFunction,
+    LetInLexicalBinding,
+    // `for-in` or `for-of` with an initialized declaration
+    ForLoopInitializer(&'static str),
+    NewTargetOutsideFunction,
+    ImportMetaOutsideModule,
+    OptionalChainInNew,
+    OptionalChainInTaggedTemplate,
+    UnaryBeforeExponent,
+    // `a ?? b || c` without parentheses
+    MixedCoalesce,
+    CommaAfterRestElement,
+    // a member expression in the parameters of an arrow function
+    BindingMemberExpression,
+    // a parenthesized name or pattern in the parameters of an arrow function
+    ParenthesizedPattern,
+    // a constructor with a modifier, like `get constructor() {}`
+    InvalidConstructor(&'static str),
+    DuplicateConstructor,
+    StaticPrototype,
+    FieldNamedConstructor,
+    // `#constructor`
+    PrivateNameConstructor,
+    DuplicatePrivateName,
+    UndeclaredPrivateName,
+    // `a + #b in c`, a private name must be on the left of `in`
+    PrivateNameNotLeft,
+    PrivateFieldDelete,
+    // `await` or `arguments` in `static {}`
+    InvalidInStaticBlock(&'static str),
+    SuperOutsideMethod,
+    SuperCallOutsideConstructor,
+    // `{a = 1}` outside of a pattern
+    ShorthandPropertyAssign,
+    // a declared array or object pattern without `= value`
+    MissingPatternInitializer,
+    // a keyword like `\u0076ar` written with a Unicode escape
+    EscapedKeyword,
+    ReservedWord(&'static str),
+    StrictModeReservedWord(&'static str),
+    // `eval` or `arguments` declared or assigned to in strict mode
+    StrictModeBinding(&'static str),
+    DuplicateParameter,
+    // syntax added in a later version than `Options::version`
+    FeatureRequiresVersion { feature: &'static str, version: EcmaVersion },
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let message = match *self {
+            ParseErrorKind::ExpectedUnicodeEscape => "Expected Unicode escape",
+            ParseErrorKind::IdentifierDirectlyAfterNumber => "Identifier directly after number",
+            ParseErrorKind::InvalidNumber => "Invalid number",
+            ParseErrorKind::InvalidRegexpFlag => "Invalid regexp flag",
+            ParseErrorKind::InvalidValue => "Invalid value",
+            ParseErrorKind::InvalidUnicodeEscape => "Invalid Unicode escape",
+            ParseErrorKind::BadEscapeSequence => "Bad character escape sequence",
+            ParseErrorKind::CodePointOutOfBounds => "Code point out of bounds",
+            ParseErrorKind::OctalInStrictMode => "Octal literal in strict mode",
+            ParseErrorKind::LeadingZeroInStrictMode => "Decimals with leading zeros are not allowed in strict mode",
+            ParseErrorKind::MisplacedNumericSeparator => "Numeric separators are only allowed between digits",
+            ParseErrorKind::InvalidBigInt => "Invalid BigInt literal",
+            ParseErrorKind::UnexpectedCharacter => "Unexpected character",
+            ParseErrorKind::UnterminatedComment => "Unterminated comment",
+            ParseErrorKind::UnterminatedRegexp => "Unterminated regexp",
+            ParseErrorKind::UnterminatedStringConstant => "Unterminated string constant",
+            ParseErrorKind::UnterminatedTemplate => "Unterminated template",
+            ParseErrorKind::OctalInTemplate => "Octal escape sequences are not allowed in template strings",
+            ParseErrorKind::UnexpectedToken => "Unexpected token",
+            ParseErrorKind::AssigningToRvalue => "Assigning to rvalue",
+            ParseErrorKind::IllegalNewlineAfterThrow => "Illegal newline after throw",
+            ParseErrorKind::InvalidAccessorArity => "Invalid number of getter or setter parameters",
+            ParseErrorKind::LabelAlreadyDeclared => "Label already declared",
+            ParseErrorKind::MissingCatchOrFinally => "Missing catch or finally clause",
+            ParseErrorKind::MultipleDefaultClauses => "Multiple default clauses",
+            ParseErrorKind::ReturnOutsideFunction => "'return' outside of function",
+            ParseErrorKind::UnsyntacticBreak => "Unsyntactic break",
+            ParseErrorKind::UnsyntacticContinue => "Unsyntactic continue",
+            ParseErrorKind::WithInStrictMode => "'with' in strict mode",
+            ParseErrorKind::ImportExportOutsideModule => "'import' and 'export' may appear only in modules",
+            ParseErrorKind::ImportExportNotTopLevel => "'import' and 'export' may only appear at the top level",
+            ParseErrorKind::AwaitInModule => "Cannot use 'await' as an identifier in a module",
+            ParseErrorKind::YieldInGenerator => "Cannot use 'yield' as identifier inside a generator",
+            ParseErrorKind::AwaitInAsyncFunction => "Cannot use 'await' as identifier inside an async function",
+            ParseErrorKind::LetInLexicalBinding => "let is disallowed as a lexically bound name",
+            ParseErrorKind::ForLoopInitializer(kind) => return write!(f, "{} loop variable declaration may not have an initializer", kind),
+            ParseErrorKind::NewTargetOutsideFunction => "'new.target' can only be used in functions",
+            ParseErrorKind::ImportMetaOutsideModule => "Cannot use 'import.meta' outside a module",
+            ParseErrorKind::OptionalChainInNew => "Optional chaining cannot appear in the callee of new expressions",
+            ParseErrorKind::OptionalChainInTaggedTemplate => "Optional chaining cannot appear in the tag of tagged template expressions",
+            ParseErrorKind::UnaryBeforeExponent => "Unary operator used immediately before exponentiation expression, parentheses must be used to disambiguate operator precedence",
+            ParseErrorKind::MixedCoalesce => "Logical expressions and coalesce expressions cannot be mixed. Wrap either by parentheses",
+            ParseErrorKind::CommaAfterRestElement => "Comma is not permitted after the rest element",
+            ParseErrorKind::BindingMemberExpression => "Binding member expression",
+            ParseErrorKind::ParenthesizedPattern => "Parenthesized pattern",
+            ParseErrorKind::InvalidConstructor(reason) => return write!(f, "Constructor can't {}", reason),
+            ParseErrorKind::DuplicateConstructor => "Duplicate constructor in the same class",
+            ParseErrorKind::StaticPrototype => "Classes may not have a static property named prototype",
+            ParseErrorKind::FieldNamedConstructor => "Classes can't have a field named 'constructor'",
+            ParseErrorKind::PrivateNameConstructor => "Classes can't have an element named '#constructor'",
+            ParseErrorKind::DuplicatePrivateName => "Private name has already been declared in the same class",
+            ParseErrorKind::UndeclaredPrivateName => "Private name must be declared in an enclosing class",
+            ParseErrorKind::PrivateNameNotLeft => "Private identifier can only be left side of binary expression",
+            ParseErrorKind::PrivateFieldDelete => "Private fields can not be deleted",
+            ParseErrorKind::InvalidInStaticBlock(word) => return write!(f, "Cannot use '{}' in class static initialization block", word),
+            ParseErrorKind::SuperOutsideMethod => "'super' keyword outside a method",
+            ParseErrorKind::SuperCallOutsideConstructor => "super() call outside constructor of a subclass",
+            ParseErrorKind::ShorthandPropertyAssign => "Shorthand property assignments are valid only in destructuring patterns",
+            ParseErrorKind::MissingPatternInitializer => "Complex binding patterns require an initialization value",
+            ParseErrorKind::EscapedKeyword => "Escape sequence in keyword",
+            ParseErrorKind::ReservedWord(word) => return write!(f, "The keyword '{}' is reserved", word),
+            ParseErrorKind::StrictModeReservedWord(word) => return write!(f, "The keyword '{}' is reserved in strict mode", word),
+            ParseErrorKind::StrictModeBinding(word) => return write!(f, "Binding '{}' in strict mode", word),
+            ParseErrorKind::DuplicateParameter => "Duplicate parameter name not allowed in strict mode",
+            ParseErrorKind::FeatureRequiresVersion { feature, version } => {
+                return write!(f, "{} requires {} or later", feature, version.name())
+            }
+        };
+        f.write_str(message)
+    }
+}
+
+#[derive(Debug)]
+pub struct ParseError {
+    pub pos: usize,
+    pub kind: ParseErrorKind,
+    // filled in once the error leaves the tokenizer or parser
+    pub loc: SourceLocation
+}
+
+impl ParseError {
+    pub fn new(kind: ParseErrorKind, pos: usize) -> ParseError {
+        ParseError { pos, kind, loc: SourceLocation::default() }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ({}:{})", self.kind, self.loc.start.line, self.loc.start.column)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+pub type ParseResult<T> = Result<T, ParseError>;
+
+// Where the tokenizer is relative to a template literal
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TemplateState {
+    // regular code
+    Outside,
+    // after '`' or the '}' closing a substitution, a chunk comes next
+    Chunk,
+    // after a chunk, either '`' or '${' comes next
+    Boundary
+}
+
+pub struct Tokenizer<'a> {
+    options: Options,
+    contains_esc: bool,
+    input: &'a str,
+    input_len: usize,
+    line_index: LineIndex,
+    tok_pos: usize,
+    // start and end of current token
+    tok_start: usize,
+    tok_end: usize,
+    // whether a '/' starts a regular expression, determined
+    // by the type of the previous token
+    regexp_allowed: bool,
+    template_state: TemplateState,
+    // brace depth inside each open '${' substitution, innermost last
+    template_braces: Vec<usize>,
+    strict: bool,
+    // the last legacy octal number or escape read in sloppy mode,
+    // with the error it is in strict mode
+    last_strict_mode_error: Option<(ParseErrorKind, usize)>,
+    // comments skipped so far, if `Options::collect_comments` is set
+    comments: Vec<Comment<'a>>,
+    // errors recovered from in tolerant mode
+    errors: Vec<ParseError>,
+    // set once the iterator reached the end of input or an error
+    finished: bool
+}
+
+impl<'a> Tokenizer<'a> {
+    pub fn new(input: &'a str, options: Options) -> Tokenizer<'a> {
+        // module code is always strict
+        let strict = options.is_module();
+        Tokenizer {
+            options,
+            contains_esc: false,
+            input,
+            input_len: input.len(),
+            line_index: LineIndex::new(input),
+            tok_pos: 0,
+            tok_start: 0,
+            tok_end: 0,
+            regexp_allowed: true,
+            template_state: TemplateState::Outside,
+            template_braces: Vec::new(),
+            strict,
+            last_strict_mode_error: None,
+            comments: Vec::new(),
+            errors: Vec::new(),
+            finished: false
+        }
+    }
+
+    /// Reads all tokens up to the end of input, not including `Eof`,
+    /// or returns the errors encountered. In tolerant mode, iterate
+    /// over `by_ref()` and check `errors` to keep the tokens.
+    pub fn tokenize_all(mut self) -> Result<Vec<Token<'a>>, Vec<ParseError>> {
+        let mut tokens = Vec::new();
+        let mut errors = Vec::new();
+        for result in self.by_ref() {
+            match result {
+                Ok(token) => tokens.push(token),
+                Err(e) => errors.push(e)
+            }
+        }
+        errors.append(&mut self.errors);
+        if errors.is_empty() { Ok(tokens) } else { Err(errors) }
+    }
+
+    pub(crate) fn input(&self) -> &'a str {
+        self.input
+    }
+
+    // Slices of the input outlive the tokenizer
+    fn slice(&self, start: usize, end: usize) -> &'a str {
+        &self.input[start..end]
+    }
+
+    pub(crate) fn options(&self) -> &Options {
+        &self.options
+    }
+
+    // Rejects `feature` at `pos` if it's newer than the targeted version
+    pub(crate) fn require_version(&self, feature: &'static str, version: EcmaVersion, pos: usize) -> ParseResult<()> {
+        if self.options.at_least(version) {
+            Ok(())
+        } else {
+            Err(ParseError::new(ParseErrorKind::FeatureRequiresVersion { feature, version }, pos))
+        }
+    }
+
+    pub(crate) fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    pub(crate) fn set_strict(&mut self, strict: bool) {
+        self.strict = strict;
+    }
+
+    // A "use strict" directive also applies to the tokens read before
+    // it was seen, the parser checks them with this.
+    pub(crate) fn last_strict_mode_error(&self) -> Option<ParseError> {
+        self.last_strict_mode_error.map(|(kind, pos)| ParseError::new(kind, pos))
+    }
+
+    // Checks that `word` at `pos` may be used as an identifier,
+    // `binding` is set if it's declared or assigned to. `strict` is
+    // passed in, since function parameters are checked after the body.
+    pub(crate) fn check_identifier(&self, word: &str, pos: usize, strict: bool, binding: bool) -> ParseResult<()> {
+        let reserved_words: &[&'static str] = match self.options.version {
+            EcmaVersion::Ecma3 => &ECMA3_RESERVED_WORDS,
+            EcmaVersion::Ecma5 => &ECMA5_RESERVED_WORDS,
+            _ => &ECMA6_RESERVED_WORDS
+        };
+        // keywords are only read as names when written with escapes
+        if self.is_keyword(word) {
+            return Err(ParseError::new(ParseErrorKind::EscapedKeyword, pos))
+        }
+        let find = |words: &[&'static str]| words.iter().copied().find(|&w| w == word);
+        if let Some(word) = find(reserved_words) {
+            return Err(ParseError::new(ParseErrorKind::ReservedWord(word), pos))
+        }
+        if strict {
+            if let Some(word) = find(&STRICT_MODE_RESERVED_WORDS) {
+                return Err(ParseError::new(ParseErrorKind::StrictModeReservedWord(word), pos))
+            }
+        }
+        if strict && binding {
+            if let Some(word) = find(&STRICT_MODE_BAD_WORDS) {
+                return Err(ParseError::new(ParseErrorKind::StrictModeBinding(word), pos))
+            }
+        }
+        Ok(())
+    }
+
+    // `yield` and `await` are names to the tokenizer and a `)` may end
+    // an expression or a statement header, the parser calls this when
+    // the current token starts an expression or a statement, so that
+    // a '/' after it starts a regular expression
+    pub(crate) fn allow_regexp(&mut self) {
+        self.regexp_allowed = true;
+    }
+
+    // Looks past the whitespace and comments after the current token,
+    // without reading anything. Returns where the next token starts and
+    // whether a newline comes before it. The parser uses this to tell
+    // contextual keywords like `let` and `async` from names.
+    pub(crate) fn peek_token_start(&self) -> (usize, bool) {
+        let mut pos = self.tok_pos;
+        let mut newline = false;
+        while pos < self.input_len {
+            let ch = self.char_at(pos);
+            let rest = &self.input[pos..];
+            if Tokenizer::is_new_line(ch) {
+                newline = true;
+                pos += ch.len_utf8();
+            } else if Tokenizer::is_whitespace(ch) {
+                pos += ch.len_utf8();
+            } else if rest.starts_with("//") {
+                pos = rest.find(Tokenizer::is_new_line).map_or(self.input_len, |i| pos + i);
+            } else if rest.starts_with("/*") {
+                let end = index_of_with_offset(self.input, "*/", pos + 2).map_or(self.input_len, |i| i + 2);
+                newline |= self.input[pos..end].contains(Tokenizer::is_new_line);
+                pos = end;
+            } else {
+                break;
+            }
+        }
+        (pos, newline)
+    }
+
+    // The unescaped word at `pos`, empty if there is none
+    pub(crate) fn word_at(&self, pos: usize) -> &str {
+        let rest = &self.input[pos..];
+        if !rest.chars().next().is_some_and(|ch| Tokenizer::is_identifier_start(ch as u32)) {
+            return ""
+        }
+        let end = rest.find(|ch: char| !Tokenizer::is_identifier_char(ch as u32)).unwrap_or(rest.len());
+        &rest[..end]
+    }
+
+    // Errors in tokens that are still complete, like a number that
+    // needs a later version, are recorded in tolerant mode
+    fn recoverable(&mut self, result: ParseResult<()>) -> ParseResult<()> {
+        match result {
+            Err(e) if self.options.tolerant => {
+                let loc = self.location(e.pos, e.pos);
+                self.errors.push(ParseError { loc, ..e });
+                Ok(())
+            },
+            result => result
+        }
+    }
+
+    fn check_version(&mut self, feature: &'static str, version: EcmaVersion, pos: usize) -> ParseResult<()> {
+        let result = self.require_version(feature, version, pos);
+        self.recoverable(result)
+    }
+
+    // `kind` is an error at `pos` in strict mode only
+    fn strict_mode_error(&mut self, kind: ParseErrorKind, pos: usize) -> ParseResult<()> {
+        if self.strict {
+            return self.recoverable(Err(ParseError::new(kind, pos)))
+        }
+        self.last_strict_mode_error = Some((kind, pos));
+        Ok(())
+    }
+
+    fn init_token_state(&mut self) -> ParseResult<()> {
+        self.skip_space()
+    }
+
+    fn curr_char(&self) -> char {
+        self.char_at(self.tok_pos)
+    }
+
+    #[inline]
+    fn curr_char_code(&self) -> u32 {
+        self.curr_char() as u32
+    }
+
+    // Positions are byte offsets into the input,
+    // reading past the end of input yields '\0'
+    fn char_at(&self, pos: usize) -> char {
+        match self.input.get(pos..) {
+            Some(rest) => rest.chars().next().unwrap_or('\0'),
+            None => '\0'
+        }
+    }
+
+
+    fn skip_space(&mut self) -> ParseResult<()> {
+        if self.tok_pos == 0 && self.input.starts_with("#!") {
+            self.skip_line_comment(CommentKind::Hashbang, 2);
+        }
+        let space_start = self.tok_pos;
+        while self.tok_pos < self.input_len {
+            let original_ch = self.curr_char();
+            let ch = original_ch as u32;
+            if ch == 32 {
+                self.tok_pos +=1;
+            } else if ch == 13 {
+                self.tok_pos +=1;
+                let next = self.curr_char() as u32;
+                if next == 10 {
+                    self.tok_pos +=1;
+                }
+            } else if ch == 10 || ch == 8232 || ch == 8233 || Tokenizer::is_whitespace(original_ch) {
+                self.tok_pos += original_ch.len_utf8();
+            } else if ch == 47 { // '/'
+                let next = self.char_at(self.tok_pos + 1) as u32;
+                if next == 42 { // '*'
+                    self.skip_block_comment()?;
+                } else if next == 47 { // '/'
+                    self.skip_line_comment(CommentKind::Line, 2);
+                } else {
+                    break;
+                }
+            } else if ch == 60 && !self.options.is_module() && self.input[self.tok_pos..].starts_with("<!--") {
+                self.skip_line_comment(CommentKind::Html, 4);
+            } else if ch == 45 && !self.options.is_module() && self.input[self.tok_pos..].starts_with("-->")
+                && self.at_line_start(space_start) {
+                self.skip_line_comment(CommentKind::Html, 3);
+            } else {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    fn skip_block_comment(&mut self) -> ParseResult<()> {
+        let start = self.tok_pos;
+        self.tok_pos +=2;
+        match index_of_with_offset(self.input, "*/", self.tok_pos) {
+            Some(i) => {
+                self.tok_pos = i + 2;
+                self.push_comment(CommentKind::Block, start, start + 2, i);
+                Ok(())
+            },
+            None => Err(ParseError::new(ParseErrorKind::UnterminatedComment, self.tok_pos - 2))
+        }
+    }
+
+    // `-->` only starts a comment if nothing but whitespace and comments
+    // precede it on its line
+    fn at_line_start(&self, space_start: usize) -> bool {
+        space_start == 0 || self.input[space_start..self.tok_pos].contains(Tokenizer::is_new_line)
+    }
+
+    fn skip_line_comment(&mut self, kind: CommentKind, start_skip: usize){
+        let start = self.tok_pos;
+        self.tok_pos += start_skip;
+        let mut ch = self.curr_char();
+        while self.tok_pos < self.input_len && !Tokenizer::is_new_line(ch) {
+            self.tok_pos += ch.len_utf8();
+            ch = self.curr_char();
+        }
+        self.push_comment(kind, start, start + start_skip, self.tok_pos);
+    }
+
+    // `start` is the start of the comment, `text_start` and `text_end`
+    // surround its text, the comment ends at `tok_pos`.
+    fn push_comment(&mut self, kind: CommentKind, start: usize, text_start: usize, text_end: usize) {
+        if !self.options.collect_comments {
+            return
+        }
+        let loc = self.location(start, self.tok_pos);
+        let text = self.slice(text_start, text_end);
+        self.comments.push(Comment { kind, text, start, end: self.tok_pos, loc });
+    }
+
+    /// Comments skipped so far, in source order. Only collected
+    /// if `Options::collect_comments` is set.
+    pub fn comments(&self) -> &[Comment<'a>] {
+        &self.comments
+    }
+
+    /// Errors recovered from so far in tolerant mode, in the order
+    /// they were found. Other errors are returned instead.
+    pub fn errors(&self) -> &[ParseError] {
+        &self.errors
+    }
+
+    pub(crate) fn take_errors(&mut self) -> Vec<ParseError> {
+        std::mem::take(&mut self.errors)
+    }
+
+    // Reads the next token, locating the error if there is one. In
+    // tolerant mode the error is recorded, and the source of the token
+    // is skipped and returned as an `Invalid` token.
+    pub(crate) fn next_token(&mut self) -> ParseResult<Token<'a>> {
+        let e = match self.read_token() {
+            Ok(token) => return Ok(token),
+            Err(e) => e
+        };
+        let loc = self.location(e.pos, e.pos);
+        let e = ParseError { loc, ..e };
+        if !self.options.tolerant {
+            return Err(e)
+        }
+        let (kind, pos) = (e.kind, e.pos);
+        self.errors.push(e);
+        Ok(self.skip_invalid_token(kind, pos))
+    }
+
+    // Skips the rest of a token that failed with `kind` at `pos`. The
+    // token started at `tok_start` and the error left `tok_pos` inside
+    // of it, so where it ends is guessed from how it starts.
+    fn skip_invalid_token(&mut self, kind: ParseErrorKind, pos: usize) -> Token<'a> {
+        let first = self.char_at(self.tok_start);
+        // always skip at least one char
+        self.tok_pos = self.tok_pos.max(self.tok_start + first.len_utf8()).min(self.input_len);
+        if kind == ParseErrorKind::UnterminatedComment {
+            // comments are skipped before the token starts
+            self.tok_start = pos;
+            self.tok_pos = self.input_len;
+        } else if self.template_state != TemplateState::Outside || kind == ParseErrorKind::UnterminatedTemplate {
+            // give up on the template, up to its closing '`'
+            self.template_state = TemplateState::Outside;
+            self.template_braces.clear();
+            self.tok_pos = index_of_with_offset(self.input, "`", self.tok_pos).map_or(self.input_len, |i| i + 1);
+        } else if first == '"' || first == '\'' {
+            // close the string at its quote or the end of the line
+            let mut escaped = false;
+            while self.tok_pos < self.input_len {
+                let ch = self.curr_char();
+                if Tokenizer::is_new_line(ch) && !escaped { break; }
+                self.tok_pos += ch.len_utf8();
+                if ch == first && !escaped { break; }
+                escaped = !escaped && ch == '\\';
+            }
+        } else if kind == ParseErrorKind::UnterminatedRegexp {
+            self.tok_pos = self.input[self.tok_pos..].find(Tokenizer::is_new_line).map_or(self.input_len, |i| self.tok_pos + i);
+        } else if first == '/' || first == '.' || first == '\\' || Tokenizer::is_identifier_char(first as u32) {
+            // the rest of a word, number or regexp flags
+            while self.tok_pos < self.input_len {
+                let ch = self.curr_char();
+                if !Tokenizer::is_identifier_char(ch as u32) && ch != '\\' { break; }
+                self.tok_pos += ch.len_utf8();
+            }
+        }
+        let text = self.slice(self.tok_start, self.tok_pos);
+        self.finish_token_with_value(TokenType::Invalid, Cow::Borrowed(text))
+    }
+
+    pub(crate) fn location(&self, start: usize, end: usize) -> SourceLocation {
+        self.line_index.location(start, end)
+    }
+
+    fn read_token(&mut self) -> ParseResult<Token<'a>> {
+        // whitespace inside a template literal is significant
+        if self.template_state != TemplateState::Outside {
+            self.tok_start = self.tok_pos;
+            return self.read_template_token()
+        }
+        self.init_token_state()?;
+        self.tok_start = self.tok_pos;
+        if self.tok_pos >= self.input_len {
+            let loc = self.location(self.tok_start, self.tok_start);
+            return Ok(Token {value: None, token_type: TokenType::Eof, source: "", start: self.tok_start, end: self.tok_start, loc, regex: None, raw: None, number: None, bigint: None, contextual: false})
+        }
+        let code = self.curr_char() as u32;
+
+        // Identifier or keyword. '\uXXXX' sequences are allowed in
+        // identifiers, so '\' also goes to that.
+        if Tokenizer::is_identifier_start(code) || code == 92 /* '\' */ {
+            return self.read_word()
+        }
+        match self.read_token_from_code(code) {
+            Err(ParseError { kind: ParseErrorKind::UnexpectedCharacter, pos, .. }) => {
+                  // If we are here, we either found a non-ASCII identifier
+                  // character, or something that's entirely disallowed.
+                  if Tokenizer::is_non_ascii_identifier_start(code) {
+                      self.read_word()
+                  } else {
+                      Err(ParseError::new(ParseErrorKind::UnexpectedCharacter, pos))
+                  }
+            },
+            result => result
+        }
+    }
+
+    fn read_token_from_code(&mut self, code: u32) -> ParseResult<Token<'a>> {
+        match code {
+            // The interpretation of a dot depends on whether it is followed
+            // by a digit or another two dots.
+            46 => self.read_token_dot(), // '.'
+            40 => {
+                self.tok_pos += 1;
+                Ok(self.finish_token(TokenType::Punc(PAREN_L)))
+            },
+            41 => {
+                self.tok_pos += 1;
+                Ok(self.finish_token(TokenType::Punc(PAREN_R)))
+            },
+            59 => {
+                self.tok_pos += 1;
+                Ok(self.finish_token(TokenType::Punc(SEMI)))
+            },
+            44 => {
+                self.tok_pos += 1;
+                Ok(self.finish_token(TokenType::Punc(COMMA)))
+            },
+            91 => {
+                self.tok_pos += 1;
+                Ok(self.finish_token(TokenType::Punc(BRACKET_L)))
+            },
+            93 => {
+                self.tok_pos += 1;
+                Ok(self.finish_token(TokenType::Punc(BRACKET_R)))
+            },
+            123 => {
+                if let Some(depth) = self.template_braces.last_mut() {
+                    *depth += 1;
+                }
+                self.tok_pos += 1;
+                Ok(self.finish_token(TokenType::Punc(BRACE_L)))
+            },
+            125 => {
+                match self.template_braces.last_mut() {
+                    // this brace closes a template substitution
+                    Some(0) => {
+                        self.template_braces.pop();
+                        self.template_state = TemplateState::Chunk;
+                    },
+                    Some(depth) => *depth -= 1,
+                    None => {}
+                }
+                self.tok_pos += 1;
+                Ok(self.finish_token(TokenType::Punc(BRACE_R)))
+            },
+            58 => {
+                self.tok_pos += 1;
+                Ok(self.finish_token(TokenType::Punc(COLON)))
+            },
+            96 => {
+                if !self.options.is_ecma6() {
+                    return Err(ParseError::new(ParseErrorKind::UnexpectedCharacter, self.tok_pos))
+                }
+                self.tok_pos += 1;
+                self.template_state = TemplateState::Chunk;
+                Ok(self.finish_token(TokenType::Punc(BQUOTE)))
+            },
+            48 => {
+                let next = self.char_at(self.tok_pos + 1) as u32;
+                if next == 120 || next == 88 { // 0x 0X hex number
+                    return self.read_radix_number(16)
+                }
+                if self.options.is_ecma6() {
+                    if next == 111 || next == 79 {
+                        return self.read_radix_number(8) // 0o 0O octal number
+                    }
+                    if next == 98 || next == 66 {
+                        return self.read_radix_number(2) // 0b 0B binary number
+                    }
+                }
+                // '0', '0.5', or a legacy octal number like '017'
+                self.read_number(false)
+            },
+            49..=57 => self.read_number(false),
+            34 | 39 => self.read_string_from_code(code),
+
+            // Operators are parsed inline in tiny state machines. '=' (61) is
+            // often referred to. `finish_op` simply skips the amount of
+            // characters it is given as second argument, and returns a token
+            // of the type given by its first argument.
+            47 => self.read_token_slash(), // '/'
+            37 | 42 => self.read_token_mult_modulo(code), // '%*'
+            124 | 38 => self.read_token_pipe_amp(code), // '|&'
+            94 => Ok(self.read_token_caret()), // '^'
+            43 | 45 => Ok(self.read_token_plus_min(code)), // '+-'
+            60 | 62 => Ok(self.read_token_lt_gt(code)), // '<>'
+            61 | 33 => Ok(self.read_token_eq_excl(code)), // '=!'
+            63 => self.read_token_question(), // '?'
+            35 => self.read_token_number_sign(), // '#'
+            126 => Ok(self.finish_op(TokenType::Operator(PREFIX), 1)), // '~'
+            _ => Err(ParseError::new(ParseErrorKind::UnexpectedCharacter, self.tok_pos))
+        }
+    }
+
+    fn read_token_dot(&mut self) -> ParseResult<Token<'a>> {
+        let next = self.char_at(self.tok_pos + 1) as u32;
+        if (48..=57).contains(&next) {
+            return self.read_number(true)
+        }
+        let next2 = self.char_at(self.tok_pos + 2) as u32;
+        if self.options.is_ecma6() && next == 46 && next2 == 46 { // '.'
+            self.tok_pos += 3;
+            Ok(self.finish_token(TokenType::Punc(ELLIPSIS)))
+        } else {
+            self.tok_pos += 1;
+            Ok(self.finish_token(TokenType::Punc(DOT)))
+        }
+    }
+
+    fn read_token_number_sign(&mut self) -> ParseResult<Token<'a>> { // '#'
+        let next = self.char_at(self.tok_pos + 1) as u32;
+        if !Tokenizer::is_identifier_start(next) && next != 92 { // '\'
+            return Err(ParseError::new(ParseErrorKind::UnexpectedCharacter, self.tok_pos))
+        }
+        self.check_version("Private name", EcmaVersion::Ecma2022, self.tok_pos)?;
+        self.tok_pos += 1;
+        let word = self.read_word_in_loop()?;
+        Ok(self.finish_token_with_value(TokenType::PrivateName, word))
+    }
+
+    fn read_token_slash(&mut self) -> ParseResult<Token<'a>> { // '/'
+        if self.regexp_allowed {
+            self.tok_pos += 1;
+            return self.read_regexp()
+        }
+        let next = self.char_at(self.tok_pos + 1) as u32;
+        if next == 61 { // '/='
+            Ok(self.finish_op(TokenType::Operator(ASSIGN), 2))
+        } else {
+            Ok(self.finish_op(TokenType::Operator(SLASH), 1))
+        }
+    }
+
+    fn read_token_mult_modulo(&mut self, code: u32) -> ParseResult<Token<'a>> { // '%*'
+        let next = self.char_at(self.tok_pos + 1) as u32;
+        if code == 42 && next == 42 { // '**'
+            if self.char_at(self.tok_pos + 2) as u32 == 61 { // '**='
+                self.check_version("'**='", EcmaVersion::Ecma2016, self.tok_pos)?;
+                return Ok(self.finish_op(TokenType::Operator(ASSIGN), 3))
+            }
+            self.check_version("'**'", EcmaVersion::Ecma2016, self.tok_pos)?;
+            return Ok(self.finish_op(TokenType::Operator(EXPONENT), 2))
+        }
+        if next == 61 { // '%=' '*='
+            return Ok(self.finish_op(TokenType::Operator(ASSIGN), 2))
+        }
+        let operator = if code == 42 { STAR } else { MODULO };
+        Ok(self.finish_op(TokenType::Operator(operator), 1))
+    }
+
+    fn read_token_pipe_amp(&mut self, code: u32) -> ParseResult<Token<'a>> { // '|&'
+        let next = self.char_at(self.tok_pos + 1) as u32;
+        if next == code { // '||' '&&'
+            if self.char_at(self.tok_pos + 2) as u32 == 61 { // '||=' '&&='
+                self.check_version("Logical assignment", EcmaVersion::Ecma2021, self.tok_pos)?;
+                return Ok(self.finish_op(TokenType::Operator(ASSIGN), 3))
+            }
+            let operator = if code == 124 { LOGICAL_OR } else { LOGICIAL_AND };
+            return Ok(self.finish_op(TokenType::Operator(operator), 2))
+        }
+        if next == 61 { // '|=' '&='
+            return Ok(self.finish_op(TokenType::Operator(ASSIGN), 2))
+        }
+        let operator = if code == 124 { BITWISE_OR } else { BITWISE_AND };
+        Ok(self.finish_op(TokenType::Operator(operator), 1))
+    }
+
+    fn read_token_caret(&mut self) -> Token<'a> { // '^'
+        let next = self.char_at(self.tok_pos + 1) as u32;
+        if next == 61 { // '^='
+            self.finish_op(TokenType::Operator(ASSIGN), 2)
+        } else {
+            self.finish_op(TokenType::Operator(BITWISE_XOR), 1)
+        }
+    }
+
+    fn read_token_plus_min(&mut self, code: u32) -> Token<'a> { // '+-'
+        let next = self.char_at(self.tok_pos + 1) as u32;
+        if next == code { // '++' '--'
+            return self.finish_op(TokenType::Operator(INC_DEC), 2)
+        }
+        if next == 61 { // '+=' '-='
+            return self.finish_op(TokenType::Operator(ASSIGN), 2)
+        }
+        self.finish_op(TokenType::Operator(PLUS_MIN), 1)
+    }
+
+    fn read_token_lt_gt(&mut self, code: u32) -> Token<'a> { // '<>'
+        let next = self.char_at(self.tok_pos + 1) as u32;
+        if next == code { // '<<' '>>' '>>>'
+            let size = if code == 62 && self.char_at(self.tok_pos + 2) as u32 == 62 { 3 } else { 2 };
+            if self.char_at(self.tok_pos + size) as u32 == 61 { // '<<=' '>>=' '>>>='
+                return self.finish_op(TokenType::Operator(ASSIGN), size + 1)
+            }
+            return self.finish_op(TokenType::Operator(BIT_SHIFT), size)
+        }
+        if next == 61 { // '<=' '>='
+            return self.finish_op(TokenType::Operator(RELATIONAL), 2)
+        }
+        self.finish_op(TokenType::Operator(RELATIONAL), 1)
+    }
+
+    fn read_token_eq_excl(&mut self, code: u32) -> Token<'a> { // '=!'
+        let next = self.char_at(self.tok_pos + 1) as u32;
+        if next == 61 { // '==' '!=' '===' '!=='
+            let size = if self.char_at(self.tok_pos + 2) as u32 == 61 { 3 } else { 2 };
+            return self.finish_op(TokenType::Operator(EQUALITY), size)
+        }
+        if code == 61 && next == 62 && self.options.is_ecma6() { // '=>'
+            self.tok_pos += 2;
+            return self.finish_token(TokenType::Punc(ARROW))
+        }
+        let operator = if code == 61 { EQ } else { PREFIX };
+        self.finish_op(TokenType::Operator(operator), 1)
+    }
+
+    fn read_token_question(&mut self) -> ParseResult<Token<'a>> { // '?'
+        let next = self.char_at(self.tok_pos + 1) as u32;
+        let next2 = self.char_at(self.tok_pos + 2) as u32;
+        if next == 63 { // '??'
+            if next2 == 61 { // '??='
+                self.check_version("Logical assignment", EcmaVersion::Ecma2021, self.tok_pos)?;
+                return Ok(self.finish_op(TokenType::Operator(ASSIGN), 3))
+            }
+            self.check_version("'??'", EcmaVersion::Ecma2020, self.tok_pos)?;
+            return Ok(self.finish_op(TokenType::Operator(NULLISH_COALESCING), 2))
+        }
+        // '?.' followed by a digit is '?' and a number, as in `a?.5:b`
+        if next == 46 && !(48..=57).contains(&next2) {
+            self.check_version("Optional chaining", EcmaVersion::Ecma2020, self.tok_pos)?;
+            self.tok_pos += 2;
+            return Ok(self.finish_token(TokenType::Punc(QUESTION_DOT)))
+        }
+        self.tok_pos += 1;
+        Ok(self.finish_token(TokenType::Punc(QUESTION)))
+    }
+
+    // Reads a decimal number or BigInt, or a legacy octal number in sloppy mode.
+    fn read_number(&mut self, starts_with_dot: bool) -> ParseResult<Token<'a>> {
+        let start = self.tok_pos;
+        // no separators in what may be a legacy octal number
+        let allow_separators = self.curr_char() != '0';
+        if !starts_with_dot && !self.read_digits(10, allow_separators)? {
+            return Err(ParseError::new(ParseErrorKind::InvalidNumber, start))
+        }
+        let integer_end = self.tok_pos;
+        // Digits after a leading zero make a legacy octal number,
+        // or a decimal one if there is an 8 or 9 (Annex B).
+        let leading_zero = self.tok_pos - start >= 2 && self.char_at(start) == '0';
+        let octal = leading_zero && !self.input[start..self.tok_pos].contains(['8', '9']);
+        if leading_zero {
+            let kind = if octal { ParseErrorKind::OctalInStrictMode } else { ParseErrorKind::LeadingZeroInStrictMode };
+            self.strict_mode_error(kind, start)?;
+        }
+        // octal numbers have no fraction or exponent
+        if !octal && self.curr_char_code() == 46 { // '.'
+            self.tok_pos += 1;
+            self.read_digits(10, true)?;
+        }
+        let mut next = self.curr_char_code();
+        if !octal && (next == 69 || next == 101) { //'eE'
+            self.tok_pos += 1;
+            next = self.curr_char_code();
+            if next == 43 || next == 45 { /* '+-' */ self.tok_pos += 1; }
+            if !self.read_digits(10, true)? {
+                return Err(ParseError::new(ParseErrorKind::InvalidNumber, start))
+            }
+        }
+        if self.curr_char() == 'n' {
+            // only plain integers can be BigInts
+            if starts_with_dot || leading_zero || self.tok_pos != integer_end {
+                return Err(ParseError::new(ParseErrorKind::InvalidBigInt, start))
+            }
+            return self.finish_bigint(start, 10)
+        }
+        if Tokenizer::is_identifier_start(self.curr_char_code()) {
+            return Err(ParseError::new(ParseErrorKind::IdentifierDirectlyAfterNumber, self.tok_pos))
+        }
+        let text = self.slice(start, self.tok_pos);
+        let text: Cow<str> = if text.contains('_') { Cow::Owned(text.replace('_', "")) } else { Cow::Borrowed(text) };
+        let value = if octal {
+            power_of_two_radix_value(&text, 8)
+        } else {
+            // Rust's float parsing is correctly rounded, and accepts
+            // everything matched above, like '.5' and '5.'
+            text.parse::<f64>().map_err(|_| ParseError::new(ParseErrorKind::InvalidNumber, start))?
+        };
+        Ok(self.finish_number(value))
+    }
+
+    fn read_word(&mut self) -> ParseResult<Token<'a>> {
+        let word = self.read_word_in_loop()?;
+        let token_type = if !self.contains_esc && self.is_keyword(&word) {
+            self.get_word_type(&word)
+        } else { TokenType::Name };
+        let contextual = token_type == TokenType::Name && !self.contains_esc && CONTEXTUAL_KEYWORDS.contains(&&*word);
+        let mut token = self.finish_token_with_value(token_type, word);
+        token.contextual = contextual;
+        Ok(token)
+    }
+
+    fn read_string_from_code(&mut self, quote_code: u32) -> ParseResult<Token<'a>> {
+        self.tok_pos += 1;
+        let mut chunk_start = self.tok_pos;
+        let mut cooked = None;
+        loop {
+            if self.tok_pos >= self.input_len {
+                return Err(ParseError::new(ParseErrorKind::UnterminatedStringConstant, self.tok_start))
+            }
+            let curr = self.curr_char();
+            let curr_code = curr as u32;
+            if quote_code == curr_code {
+                let value = self.finish_cooked(cooked, chunk_start);
+                self.tok_pos += 1;
+                return Ok(self.finish_token_with_value(TokenType::StringLiteral, value))
+            }
+            if curr_code == 92 { // '\'
+                let out = self.start_cooked(&mut cooked, chunk_start);
+                self.read_escaped_char(out)?;
+                chunk_start = self.tok_pos;
+            } else {
+                // U+2028 and U+2029 may appear in strings since ES2019,
+                // which made JSON a subset of ECMAScript
+                let ends_line = curr_code == 10 || curr_code == 13 ||
+                    Tokenizer::is_new_line(curr) && !self.options.at_least(EcmaVersion::Ecma2019);
+                if ends_line {
+                    return Err(ParseError::new(ParseErrorKind::UnterminatedStringConstant, self.tok_start))
+                }
+                self.tok_pos += curr.len_utf8();
+            }
+        }
+    }
+
+    // Reads the next token inside a template literal: a chunk of the
+    // template (possibly empty), the closing '`' or the '${' that starts
+    // a substitution.
+    fn read_template_token(&mut self) -> ParseResult<Token<'a>> {
+        if self.template_state == TemplateState::Chunk {
+            self.template_state = TemplateState::Boundary;
+            return self.read_template_chunk()
+        }
+        if self.curr_char() == '`' {
+            self.template_state = TemplateState::Outside;
+            self.tok_pos += 1;
+            return Ok(self.finish_token(TokenType::Punc(BQUOTE)))
+        }
+        // a chunk only ends at '`', '${' or the end of input
+        if self.tok_pos >= self.input_len {
+            return Err(ParseError::new(ParseErrorKind::UnterminatedTemplate, self.tok_start))
+        }
+        self.template_state = TemplateState::Outside;
+        self.template_braces.push(0);
+        self.tok_pos += 2;
+        Ok(self.finish_token(TokenType::Punc(DOLLAR_BRACE_L)))
+    }
+
+    fn read_template_chunk(&mut self) -> ParseResult<Token<'a>> {
+        let mut chunk_start = self.tok_pos;
+        let mut cooked = None;
+        loop {
+            if self.tok_pos >= self.input_len {
+                return Err(ParseError::new(ParseErrorKind::UnterminatedTemplate, self.tok_start))
+            }
+            let ch = self.curr_char();
+            if ch == '`' || (ch == '$' && self.char_at(self.tok_pos + 1) == '{') {
+                break;
+            }
+            if ch == '\\' {
+                if self.tok_pos + 1 >= self.input_len {
+                    return Err(ParseError::new(ParseErrorKind::UnterminatedTemplate, self.tok_start))
+                }
+                let next = self.char_at(self.tok_pos + 1);
+                let is_nul = next == '0' && !self.char_at(self.tok_pos + 2).is_ascii_digit();
+                if next.is_ascii_digit() && !is_nul {
+                    return Err(ParseError::new(ParseErrorKind::OctalInTemplate, self.tok_pos))
+                }
+                let out = self.start_cooked(&mut cooked, chunk_start);
+                self.read_escaped_char(out)?;
+                chunk_start = self.tok_pos;
+            } else if ch == '\r' {
+                // '\r\n' and '\r' are normalized to '\n'
+                self.start_cooked(&mut cooked, chunk_start).push('\n');
+                self.tok_pos += 1;
+                if self.curr_char() == '\n' {
+                    self.tok_pos += 1;
+                }
+                chunk_start = self.tok_pos;
+            } else {
+                self.tok_pos += ch.len_utf8();
+            }
+        }
+        let value = self.finish_cooked(cooked, chunk_start);
+        let source = self.slice(self.tok_start, self.tok_pos);
+        let raw = if source.contains('\r') {
+            Cow::Owned(source.replace("\r\n", "\n").replace('\r', "\n"))
+        } else {
+            Cow::Borrowed(source)
+        };
+        let mut token = self.finish_token_with_value(TokenType::Template, value);
+        token.raw = Some(raw);
+        Ok(token)
+    }
+
+    // The cooked value of a string or template chunk borrows from the
+    // source until an escape is found. Then the text since `chunk_start`
+    // is copied into `cooked`, which is returned for the escape.
+    fn start_cooked<'c>(&self, cooked: &'c mut Option<String>, chunk_start: usize) -> &'c mut String {
+        let out = cooked.get_or_insert_with(String::new);
+        out.push_str(self.slice(chunk_start, self.tok_pos));
+        out
+    }
+
+    fn finish_cooked(&self, cooked: Option<String>, chunk_start: usize) -> Cow<'a, str> {
+        let rest = self.slice(chunk_start, self.tok_pos);
+        match cooked {
+            Some(mut cooked) => {
+                cooked.push_str(rest);
+                Cow::Owned(cooked)
+            },
+            None => Cow::Borrowed(rest)
+        }
+    }
+
+    // Used to read escaped characters in strings. The escaped character,
+    // if any, is pushed to `out`; line continuations produce nothing.
+    fn read_escaped_char(&mut self, out: &mut String) -> ParseResult<()> {
+        let esc_start = self.tok_pos;
+        self.tok_pos += 1; // '\'
+        let ch = self.curr_char();
+        if ('0'..='7').contains(&ch) {
+            return self.read_octal_escape(out, esc_start)
+        }
+        self.tok_pos += ch.len_utf8();
+        match ch {
+            'n' => out.push('\n'),
+            'r' => out.push('\r'),
+            't' => out.push('\t'),
+            'b' => out.push('\u{8}'),
+            'v' => out.push('\u{b}'),
+            'f' => out.push('\u{c}'),
+            'x' => {
+                let code = self.read_hex_char(2)
+                    .map_err(|_| ParseError::new(ParseErrorKind::BadEscapeSequence, esc_start))?;
+                // two hex digits always make a valid char
+                out.push(char::from_u32(code).unwrap());
+            },
+            'u' => {
+                let mut code = self.read_code_point()?;
+                // combine an escaped surrogate pair into a single char
+                if (0xD800..0xDC00).contains(&code) && self.input[self.tok_pos..].starts_with("\\u") {
+                    let saved_pos = self.tok_pos;
+                    self.tok_pos += 2;
+                    match self.read_code_point() {
+                        Ok(low) if (0xDC00..0xE000).contains(&low) => {
+                            code = 0x10000 + ((code - 0xD800) << 10) + (low - 0xDC00);
+                        },
+                        _ => self.tok_pos = saved_pos
+                    }
+                }
+                // lone surrogates can't be represented in a Rust string
+                out.push(char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER));
+            },
+            '8' | '9' => {
+                self.strict_mode_error(ParseErrorKind::OctalInStrictMode, esc_start)?;
+                out.push(ch);
+            },
+            '\r' => {
+                self.check_version("Line continuation", EcmaVersion::Ecma5, esc_start)?;
+                // '\r\n' is a single line continuation
+                if self.curr_char() == '\n' {
+                    self.tok_pos += 1;
+                }
+            },
+            '\n' | '\u{2028}' | '\u{2029}' => {
+                self.check_version("Line continuation", EcmaVersion::Ecma5, esc_start)?;
+            },
+            _ => {
+                if self.tok_pos > self.input_len {
+                    return Err(ParseError::new(ParseErrorKind::UnterminatedStringConstant, self.tok_start))
+                }
+                out.push(ch);
+            }
+        }
+        Ok(())
+    }
+
+    // Reads a legacy octal escape like '\012', which can be
+    // at most 3 digits long and may not exceed 255.
+    // '\0' not followed by a digit is the NUL character.
+    fn read_octal_escape(&mut self, out: &mut String, esc_start: usize) -> ParseResult<()> {
+        let digits_start = self.tok_pos;
+        let mut code = 0;
+        while self.tok_pos - digits_start < 3 {
+            let digit = self.curr_char_code().wrapping_sub(48);
+            if digit > 7 || code * 8 + digit > 255 { break; }
+            code = code * 8 + digit;
+            self.tok_pos += 1;
+        }
+        let is_nul = code == 0 && self.tok_pos - digits_start == 1 &&
+            !self.curr_char().is_ascii_digit();
+        if !is_nul {
+            self.strict_mode_error(ParseErrorKind::OctalInStrictMode, esc_start)?;
+        }
+        // octal escapes never exceed 255, so this can't fail
+        out.push(char::from_u32(code).unwrap());
+        Ok(())
+    }
+
+    // Reads the part of a Unicode escape after '\u', either
+    // exactly four hex digits or (in ES6) '{' hex digits '}'.
+    fn read_code_point(&mut self) -> ParseResult<u32> {
+        if self.curr_char() != '{' {
+            return self.read_hex_char(4)
+        }
+        if !self.options.is_ecma6() {
+            return Err(ParseError::new(ParseErrorKind::InvalidUnicodeEscape, self.tok_pos))
+        }
+        self.tok_pos += 1;
+        let start = self.tok_pos;
+        let mut code: u32 = 0;
+        while let Some(digit) = Tokenizer::digit_value(self.curr_char_code()).filter(|&d| d < 16) {
+            code = code.saturating_mul(16).saturating_add(digit);
+            self.tok_pos += 1;
+        }
+        if self.tok_pos == start || self.curr_char() != '}' {
+            return Err(ParseError::new(ParseErrorKind::InvalidUnicodeEscape, self.tok_pos))
+        }
+        if code > 0x10FFFF {
+            return Err(ParseError::new(ParseErrorKind::CodePointOutOfBounds, start))
+        }
+        self.tok_pos += 1;
+        Ok(code)
+    }
+
+    // Classify a reserved word: literal words become `Value` tokens,
+    // word operators become `Operator` tokens and the rest keywords.
+    fn get_word_type(&self, word: &str) -> TokenType {
+        let keyword = match word {
+            "null" => return TokenType::Value(NULL),
+            "true" => return TokenType::Value(TRUE),
+            "false" => return TokenType::Value(FALSE),
+            "in" | "instanceof" => return TokenType::Operator(RELATIONAL),
+            "typeof" | "void" | "delete" => return TokenType::Operator(PREFIX),
+            "break" => BREAK,
+            "case" => CASE,
+            "catch" => CATCH,
+            "class" => CLASS,
+            "const" => CONST,
+            "continue" => CONTINUE,
+            "debugger" => DEBUGGER,
+            "default" => DEFAULT,
+            "do" => DO,
+            "else" => ELSE,
+            "export" => EXPORT,
+            "extends" => EXTENDS,
+            "finally" => FINALLY,
+            "import" => IMPORT,
+            "for" => FOR,
+            "function" => FUNCTION,
+            "if" => IF,
+            "new" => NEW,
+            "return" => RETURN,
+            "super" => SUPER,
+            "switch" => SWITCH,
+            "this" => THIS,
+            "throw" => THROW,
+            "try" => TRY,
+            "var" => VAR,
+            "while" => WHILE,
+            "with" => WITH,
+            _ => return TokenType::Name
+        };
+        TokenType::Keyword(keyword)
+    }
+
+    // The word is only allocated if it contains escapes
+    fn read_word_in_loop(&mut self) -> ParseResult<Cow<'a, str>> {
+        let start = self.tok_pos;
+        let mut first = true;
+        self.contains_esc = false;
+        let mut word = "".to_string();
+        loop {
+            let ch = self.curr_char();
+            let code = ch as u32;
+            if Tokenizer::is_identifier_char(code) {
+                if self.contains_esc {
+                    word.push(ch);
+                }
+                self.tok_pos += ch.len_utf8();
+            } else if code == 92 { /* '\'  */
+                if !self.contains_esc {
+                    word = self.input[start..self.tok_pos].to_string();
+                }
+                self.contains_esc = true;
+                self.tok_pos += 1;
+                if self.curr_char() as u32 != 117 { // 'u'
+                    return Err(ParseError::new(ParseErrorKind::ExpectedUnicodeEscape, self.tok_pos))
+                }
+                self.tok_pos += 1;
+                let esc_start = self.tok_pos;
+                let esc_code = self.read_code_point()?;
+                match char::from_u32(esc_code) {
+                    Some(ch) => {
+                        let is_identifier_char = if first {
+                                Tokenizer::is_identifier_start(esc_code)
+                            } else {
+                                Tokenizer::is_identifier_char(esc_code)
+                            };
+                        if is_identifier_char {
+                            word.push(ch);
+                        } else {
+                            return Err(ParseError::new(ParseErrorKind::InvalidUnicodeEscape, esc_start))
+                        }
+
+                    },
+                    None => return Err(ParseError::new(ParseErrorKind::InvalidUnicodeEscape, esc_start))
+                };
+            } else { break; }
+            first = false;
+        };
+
+        if self.contains_esc {
+            Ok(Cow::Owned(word))
+        } else {
+            Ok(Cow::Borrowed(self.slice(start, self.tok_pos)))
+        }
+    }
+
+    fn read_hex_char(&mut self, len: usize) -> ParseResult<u32> {
+        self.read_u32_of_len(16, len)
+            .map_err(|_| ParseError::new(ParseErrorKind::InvalidUnicodeEscape, self.tok_pos))
+    }
+
+    // Reads an unsigned integer in given radix of `len` length
+    // if zero digits were read, returns None.
+    // If integer is not of length `len`, None is returned
+    fn read_u32_of_len(&mut self, radix: u32, len: usize) -> ParseResult<u32> {
+        let start = self.tok_pos;
+        let mut total = 0;
+        for _ in 0..len {
+            let val = match Tokenizer::digit_value(self.curr_char_code()) {
+                Some(val) if val < radix => val,
+                _ => break
+            };
+            self.tok_pos += 1;
+            total = total * radix + val;
+        }
+        if self.tok_pos - start != len {
+            Err(ParseError::new(ParseErrorKind::InvalidValue, self.tok_pos))
+        } else {
+            Ok(total)
+        }
+    }
+
+    // Skips digits in given radix, and numeric separators between
+    // them if `allow_separators` is set. Returns whether any digits were read.
+    fn read_digits(&mut self, radix: u32, allow_separators: bool) -> ParseResult<bool> {
+        let start = self.tok_pos;
+        let mut after_separator = false;
+        loop {
+            let code = self.curr_char_code();
+            if code == 95 { // '_'
+                self.check_version("Numeric separators", EcmaVersion::Ecma2021, self.tok_pos)?;
+                if !allow_separators || after_separator || self.tok_pos == start {
+                    return Err(ParseError::new(ParseErrorKind::MisplacedNumericSeparator, self.tok_pos))
+                }
+                after_separator = true;
+                self.tok_pos += 1;
+                continue;
+            }
+            match Tokenizer::digit_value(code) {
+                Some(val) if val < radix => {},
+                _ => break
+            }
+            after_separator = false;
+            self.tok_pos += 1;
+        }
+        if after_separator {
+            return Err(ParseError::new(ParseErrorKind::MisplacedNumericSeparator, self.tok_pos - 1))
+        }
+        Ok(self.tok_pos > start)
+    }
+
+    fn digit_value(code: u32) -> Option<u32> {
+        if (97..=122).contains(&code) {
+            Some(code - 97 + 10) // a
+        } else if (65..=90).contains(&code) {
+            Some(code - 65 + 10) // A
+        } else if (48..=57).contains(&code) { //0-9
+            Some(code - 48)
+        } else {
+            None
+        }
+    }
+
+    fn read_radix_number(&mut self, radix: u32) -> ParseResult<Token<'a>> {
+        self.tok_pos += 2;
+        let digits_start = self.tok_pos;
+        if !self.read_digits(radix, true)? {
+            return Err(ParseError::new(ParseErrorKind::InvalidNumber, self.tok_start))
+        }
+        if self.curr_char() == 'n' {
+            return self.finish_bigint(digits_start, radix)
+        }
+        if Tokenizer::is_identifier_char(self.curr_char() as u32) {
+            return Err(ParseError::new(ParseErrorKind::IdentifierDirectlyAfterNumber, self.tok_pos))
+        }
+        let value = power_of_two_radix_value(&self.input[digits_start..self.tok_pos], radix);
+        Ok(self.finish_number(value))
+    }
+
+    // Parse a regular expression. Some context-awareness is necessary,
+    // since a '/' inside a '[]' set does not end the expression.
+    fn read_regexp(&mut self) -> ParseResult<Token<'a>> {
+        let mut escaped = false;
+        let mut in_class = false;
+        let start = self.tok_pos;
+        loop {
+            if self.tok_pos >= self.input_len {
+                return Err(ParseError::new(ParseErrorKind::UnterminatedRegexp, self.tok_start))
+            }
+            let ch = self.curr_char();
+            if Tokenizer::is_new_line(ch) {
+                return Err(ParseError::new(ParseErrorKind::UnterminatedRegexp, self.tok_start))
+            }
+            if !escaped {
+                if ch == '[' { in_class = true; }
+                else if ch == ']' && in_class { in_class = false; }
+                else if ch == '/' && !in_class { break; }
+                escaped = ch == '\\';
+            } else { escaped = false; }
+            self.tok_pos += ch.len_utf8();
+        }
+        let pattern = self.slice(start, self.tok_pos);
+        self.tok_pos += 1;
+        // flags are read as a word, but '\uXXXX' escapes are not allowed in them
+        let flags_pos = self.tok_pos;
+        self.read_word_in_loop()?;
+        if self.contains_esc {
+            return Err(ParseError::new(ParseErrorKind::InvalidRegexpFlag, flags_pos))
+        }
+        let flags = self.slice(flags_pos, self.tok_pos);
+        let valid = self.validate_regexp_flags(flags, flags_pos);
+        self.recoverable(valid)?;
+        let mut token = self.finish_token_with_value(TokenType::Regexp, Cow::Borrowed(pattern));
+        token.regex = Some(RegexpValue { pattern, flags });
+        Ok(token)
+    }
+
+    fn validate_regexp_flags(&self, flags: &str, flags_pos: usize) -> ParseResult<()> {
+        for (i, flag) in flags.char_indices() {
+            let (feature, version) = match flag {
+                'g' | 'i' | 'm' => continue,
+                'u' => ("Regexp flag 'u'", EcmaVersion::Ecma6),
+                'y' => ("Regexp flag 'y'", EcmaVersion::Ecma6),
+                's' => ("Regexp flag 's'", EcmaVersion::Ecma2018),
+                'd' => ("Regexp flag 'd'", EcmaVersion::Ecma2022),
+                'v' => ("Regexp flag 'v'", EcmaVersion::Ecma2024),
+                _ => return Err(ParseError::new(ParseErrorKind::InvalidRegexpFlag, flags_pos + i))
+            };
+            self.require_version(feature, version, flags_pos + i)?;
+        }
+        for (i, flag) in flags.char_indices() {
+            // 'u' and 'v' are mutually exclusive
+            if flags[i + 1..].contains(flag) || flag == 'v' && flags.contains('u') {
+                return Err(ParseError::new(ParseErrorKind::InvalidRegexpFlag, flags_pos + i))
+            }
+        }
+        Ok(())
+    }
+
+    fn finish_token_with_value(&mut self, token_type: TokenType, value: Cow<'a, str>) -> Token<'a> {
+        let mut token = self.finish_token(token_type);
+        token.value = Some(value);
+        token
+    }
+
+    // Numbers keep their source text as value and raw
+    fn finish_number(&mut self, number: f64) -> Token<'a> {
+        let text = self.slice(self.tok_start, self.tok_pos);
+        let mut token = self.finish_token_with_value(TokenType::Num, Cow::Borrowed(text));
+        token.raw = Some(Cow::Borrowed(text));
+        token.number = Some(number);
+        token
+    }
+
+    // Finishes a BigInt whose digits in `radix` start at `digits_start`
+    // and end at the current 'n'
+    fn finish_bigint(&mut self, digits_start: usize, radix: u32) -> ParseResult<Token<'a>> {
+        self.check_version("BigInt", EcmaVersion::Ecma2020, self.tok_start)?;
+        let digits_end = self.tok_pos;
+        self.tok_pos += 1;
+        if Tokenizer::is_identifier_char(self.curr_char_code()) {
+            return Err(ParseError::new(ParseErrorKind::IdentifierDirectlyAfterNumber, self.tok_pos))
+        }
+        let text = self.slice(self.tok_start, self.tok_pos);
+        let mut token = self.finish_token_with_value(TokenType::BigInt, Cow::Borrowed(text));
+        token.raw = Some(Cow::Borrowed(text));
+        let digits = self.slice(digits_start, digits_end);
+        token.bigint = Some(if radix == 10 && !digits.contains('_') {
+            Cow::Borrowed(digits)
+        } else {
+            Cow::Owned(to_decimal_digits(digits, radix))
+        });
+        Ok(token)
+    }
+
+    fn finish_op(&mut self, token_type: TokenType, size: usize) -> Token<'a> {
+        let op = self.slice(self.tok_pos, self.tok_pos + size);
+        self.tok_pos += size;
+        self.finish_token_with_value(token_type, Cow::Borrowed(op))
+    }
+
+    fn finish_token(&mut self, token_type: TokenType) -> Token<'a> {
+        self.tok_end = self.tok_pos;
+        self.regexp_allowed = token_type.before_expr();
+        let loc = self.location(self.tok_start, self.tok_end);
+        let source = self.slice(self.tok_start, self.tok_end);
+        Token {value: None, token_type, source, start: self.tok_start, end: self.tok_end, loc, regex: None, raw: None, number: None, bigint: None, contextual: false }
+    }
+
+    /// test if char code can start an identifier
+    fn is_identifier_start(code: u32) -> bool {
+        if code < 65 {return code == 36;}
+        if code < 91 {return true;}
+        if code < 97 {return code == 95;}
+        if code < 123 {return true;}
+        code >= 0xAA && Tokenizer::is_non_ascii_identifier_start(code)
+    }
+
+    /// test if char code can be in identifier
+    fn is_identifier_char(code: u32) -> bool {
+        if code < 48 { return code == 36; }
+        if code < 58 { return true; }
+        if code < 65 { return false; }
+        if code < 91 { return true; }
+        if code < 97 { return code == 95; }
+        if code < 123 { return true; }
+        code >= 0xAA && Tokenizer::is_non_ascii_identifier_char(code)
+    }
+
+    fn is_non_ascii_identifier_start(code: u32) -> bool {
+        unicode::in_ranges(code, unicode::ID_START)
+    }
+
+    fn is_non_ascii_identifier_char(code: u32) -> bool {
+        unicode::in_ranges(code, unicode::ID_CONTINUE)
+    }
+
+    pub(crate) fn is_keyword(&self, word: &str) -> bool {
+        match self.options.version {
+            EcmaVersion::Ecma3 => ECMA3_KEYWORDS.contains(&word),
+            EcmaVersion::Ecma5 => ECMA5_KEYWORDS.contains(&word),
+            _ => ECMA6_KEYWORDS.contains(&word)
+        }
+    }
+
+    // Whitespace that isn't a line terminator: tab, vertical tab, form
+    // feed, space, no-break space, the byte order mark and the other
+    // Unicode space separators
+    #[inline]
+    fn is_whitespace(ch: char) -> bool {
+        matches!(ch as u32, 9 | 11 | 12 | 32 | 160 | 0xfeff) ||
+            ch as u32 >= 5760 && ch.is_whitespace() && !Tokenizer::is_new_line(ch)
+    }
+
+    #[inline]
+    fn is_new_line(ch: char) -> bool {
+        matches!(ch as u32, 10 | 13 | 8232 | 8233)
+    }
+}
+
+// Yields tokens up to the end of input. An error is yielded
+// once, after which the iterator is exhausted.
+impl<'a> Iterator for Tokenizer<'a> {
+    type Item = ParseResult<Token<'a>>;
+
+    fn next(&mut self) -> Option<ParseResult<Token<'a>>> {
+        if self.finished {
+            return None
+        }
+        match self.next_token() {
+            Ok(token) =>  {
+                match token.token_type {
+                    TokenType::Eof => {
+                        self.finished = true;
+                        None
+                    },
+                    _ => Some(Ok(token))
+                }
+            },
+            Err(e) => {
+                self.finished = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+impl FusedIterator for Tokenizer<'_> {}
+
+
+fn index_of_with_offset(haystack: &str, needle: &str, offset: usize) -> Option<usize> {
+    haystack[offset..].find(needle).map(|index| index + offset)
+}
+
+// Converts an integer of any size in `radix` to decimal,
+// skipping numeric separators.
+fn to_decimal_digits(digits: &str, radix: u32) -> String {
+    const LIMB: u64 = 1_000_000_000;
+    // base 10^9, least significant first
+    let mut limbs: Vec<u64> = vec![0];
+    for digit in digits.chars().filter_map(|ch| ch.to_digit(radix)) {
+        let mut carry = u64::from(digit);
+        for limb in limbs.iter_mut() {
+            let value = *limb * u64::from(radix) + carry;
+            *limb = value % LIMB;
+            carry = value / LIMB;
+        }
+        if carry > 0 {
+            limbs.push(carry);
+        }
+    }
+    let mut out = limbs[limbs.len() - 1].to_string();
+    for limb in limbs.iter().rev().skip(1) {
+        out.push_str(&format!("{:09}", limb));
+    }
+    out
+}
+
+// Value of `digits` in radix 2, 8 or 16. Values above 2^53 are
+// rounded to the nearest float, ties to even, like JavaScript does.
+fn power_of_two_radix_value(digits: &str, radix: u32) -> f64 {
+    let bits_per_digit = radix.trailing_zeros();
+    // most significant bit first
+    let mut bits = Vec::new();
+    for digit in digits.chars().filter_map(|ch| ch.to_digit(radix)) {
+        for i in (0..bits_per_digit).rev() {
+            bits.push(digit >> i & 1 == 1);
+        }
+    }
+    let bits = match bits.iter().position(|&bit| bit) {
+        Some(first_one) => &bits[first_one..],
+        None => return 0.0
+    };
+    let to_u64 = |bits: &[bool]| bits.iter().fold(0u64, |total, &bit| total << 1 | bit as u64);
+    if bits.len() <= 53 {
+        return to_u64(bits) as f64
+    }
+    let mut mantissa = to_u64(&bits[..53]);
+    let half = bits[53];
+    let rest = bits[54..].iter().any(|&bit| bit);
+    if half && (rest || mantissa & 1 == 1) {
+        // 2^53 is still exact
+        mantissa += 1;
+    }
+    let exponent = i32::try_from(bits.len() - 53).unwrap_or(i32::MAX);
+    mantissa as f64 * 2f64.powi(exponent)
+}
@@ -0,0 +1,117 @@
+use tyrion::{Parser, Options, ParseError, ParseErrorKind, Position, SourceLocation, render_error};
+
+fn render(source: &str) -> Vec<String> {
+    let error = Parser::new(source, Options::default()).parse().unwrap_err();
+    render_error(&error, "app.js", source, false).lines().map(str::to_string).collect()
+}
+
+// Renders an error spanning from `start` to `end`, as (line, column)
+fn render_span(source: &str, pos: usize, start: (usize, usize), end: (usize, usize)) -> Vec<String> {
+    let mut error = ParseError::new(ParseErrorKind::UnexpectedToken, pos);
+    error.loc = SourceLocation {
+        start: Position { line: start.0, column: start.1 },
+        end: Position { line: end.0, column: end.1 }
+    };
+    render_error(&error, "app.js", source, false).lines().map(str::to_string).collect()
+}
+
+#[test]
+fn underlines_the_error_span() {
+    assert_eq!(render("a;\nvar a b;"), [
+        "error: Unexpected token",
+        " --> app.js:2:7",
+        "  |",
+        "2 | var a b;",
+        "  |       ^"
+    ]);
+    assert_eq!(render_span("var a = 1 bad;", 10, (1, 10), (1, 13)), [
+        "error: Unexpected token",
+        " --> app.js:1:11",
+        "  |",
+        "1 | var a = 1 bad;",
+        "  |           ^^^"
+    ]);
+}
+
+#[test]
+fn multi_line_spans_underline_their_first_line() {
+    assert_eq!(render_span("a = `b\nc` d", 4, (1, 4), (2, 2)), [
+        "error: Unexpected token",
+        " --> app.js:1:5",
+        "  |",
+        "1 | a = `b",
+        "  |     ^"
+    ]);
+}
+
+#[test]
+fn errors_at_end_of_input() {
+    assert_eq!(render("a +"), [
+        "error: Unexpected token",
+        " --> app.js:1:4",
+        "  |",
+        "1 | a +",
+        "  |    ^"
+    ]);
+    assert_eq!(render("var a = \"abc"), [
+        "error: Unterminated string constant",
+        " --> app.js:1:9",
+        "  |",
+        "1 | var a = \"abc",
+        "  |         ^"
+    ]);
+}
+
+#[test]
+fn tabs_are_kept_in_the_padding() {
+    assert_eq!(render("\tvar a b;"), [
+        "error: Unexpected token",
+        " --> app.js:1:8",
+        "  |",
+        "1 | \tvar a b;",
+        "  | \t      ^"
+    ]);
+}
+
+#[test]
+fn non_ascii_columns() {
+    assert_eq!(render("var é = 1 b"), [
+        "error: Unexpected token",
+        " --> app.js:1:11",
+        "  |",
+        "1 | var é = 1 b",
+        "  |           ^"
+    ]);
+    // columns count UTF-16 code units, the padding counts characters
+    assert_eq!(render("'😀' b"), [
+        "error: Unexpected token",
+        " --> app.js:1:6",
+        "  |",
+        "1 | '😀' b",
+        "  |     ^"
+    ]);
+}
+
+#[test]
+fn gutter_fits_the_line_number() {
+    assert_eq!(render("a\n\n\n\n\n\n\n\n\n\n  )"), [
+        "error: Unexpected token",
+        "  --> app.js:11:3",
+        "   |",
+        "11 |   )",
+        "   |   ^"
+    ]);
+}
+
+#[test]
+fn color_output() {
+    let error = Parser::new("a b", Options::default()).parse().unwrap_err();
+    assert_eq!(render_error(&error, "app.js", "a b", true), concat!(
+        "\x1b[1;31merror\x1b[0m\x1b[1m: Unexpected token\x1b[0m\n",
+        " \x1b[1;34m-->\x1b[0m app.js:1:3\n",
+        "  \x1b[1;34m|\x1b[0m\n",
+        "\x1b[1;34m1\x1b[0m \x1b[1;34m|\x1b[0m a b\n",
+        "  \x1b[1;34m|\x1b[0m   \x1b[1;31m^\x1b[0m\n"
+    ));
+    assert!(!render_error(&error, "app.js", "a b", false).contains('\x1b'));
+}
@@ -0,0 +1,51 @@
+use std::fs;
+use std::path::Path;
+use tyrion::{Parser, Options, SourceType, to_estree_json};
+
+// Each fixture is a source file next to the JSON acorn produces for it
+// with `locations` and `ranges` enabled
+fn check_fixture(name: &str, options: Options) {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("fixtures");
+    let source = fs::read_to_string(dir.join(format!("{}.js", name))).unwrap();
+    let expected = fs::read_to_string(dir.join(format!("{}.json", name))).unwrap();
+    let program = match Parser::new(&source, options).parse() {
+        Ok(program) => program,
+        Err(e) => panic!("{}.js failed to parse: {}", name, e)
+    };
+    assert_eq!(to_estree_json(&program, &source), expected.trim_end(), "{}.json", name);
+}
+
+#[test]
+fn statements() {
+    check_fixture("statements", Options::default());
+}
+
+#[test]
+fn expressions() {
+    check_fixture("expressions", Options::default());
+}
+
+#[test]
+fn module() {
+    check_fixture("module", Options { source_type: SourceType::Module, ..Options::default() });
+}
+
+#[test]
+fn utf16_offsets() {
+    check_fixture("utf16", Options::default());
+}
+
+#[test]
+fn patterns() {
+    check_fixture("patterns", Options::default());
+}
+
+#[test]
+fn classes() {
+    check_fixture("classes", Options::default());
+}
+
+#[test]
+fn arrow_functions() {
+    check_fixture("arrows", Options::default());
+}
@@ -0,0 +1,9 @@
+a => a * 2;
+(a, [b], {c}, d = 1, ...e) => { return a };
+() => ({});
+async x => await x;
+async (a, b) => {};
+async(a, b);
+f(a => b, (c) => d);
+(a => b)(c);
+x = y => z => y + z;
@@ -0,0 +1,16 @@
+class A extends B.C {
+  constructor(a) { super(a); }
+  static *gen() { yield super.x; }
+  get a() { return 1 }
+  set a(v) {}
+  async b() {}
+  ['c']() {}
+  static = 1;
+  d;
+  static e = this.f
+  static async *[g]() {}
+  #h = 1;
+  static #i(o) { return #h in o && o.#h }
+  static { this.j = 1 }
+}
+var D = class {};
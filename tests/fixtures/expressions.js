@@ -0,0 +1,11 @@
+a = b ? c : d || e && f;
+x += -y * (z + 1) ** 2;
+new Foo(1, "two").bar[0]();
+o?.p?.(q);
+[1, , this, null, /re/gi, 10n];
+({a: 1, "b": 2, get c() { return 3 }, d() {}, *[e]() {}, async f() {}});
+typeof a, void 0, i++, --j;
+g = function* () { yield 1 };
+tag`a${b}\n${`d`}`, `\u0041`;
+f(...a, [...b], {...c});
+1e21, 1.5e-7, 123456789e-20, 0.000001, 1e300, 5e-324, 0.1, -0;
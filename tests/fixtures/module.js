@@ -0,0 +1,6 @@
+import d, { a as b } from "m";
+import * as ns from "n";
+export { b as c };
+export default 42;
+export * from "m";
+for await (const x of xs) ;
@@ -0,0 +1,6 @@
+var [a, , b = 1, ...c] = d;
+let {e, f: g = 2, ["h"]: i, ...j} = k;
+function l([m], {n} = {}, ...o) {}
+[p.q, {r = 3}] = s;
+for ({t} of u);
+try {} catch ([v]) {}
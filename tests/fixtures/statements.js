@@ -0,0 +1,9 @@
+var a = 1, b;
+if (a) b = a; else {}
+for (var i = 0; i < 10; i++) continue;
+for (k in o) ;
+while (a) break;
+function f(x) { return x }
+l: do ; while (0)
+switch (a) { case 1: default: }
+try { throw a } catch (e) {} finally {}
@@ -0,0 +1,2 @@
+var s = "😀";
+/* 𝐀 */ s;
@@ -0,0 +1,656 @@
+use tyrion::ast::*;
+use tyrion::{Parser, Options, EcmaVersion, SourceType, ParseErrorKind, Position, Comment, CommentKind};
+
+fn parse_with(source: &str, options: Options) -> Program<'_> {
+    match Parser::new(source, options).parse() {
+        Ok(program) => program,
+        Err(e) => panic!("{:?} failed to parse: {}", source, e)
+    }
+}
+
+fn parse(source: &str) -> Program<'_> {
+    parse_with(source, Options::default())
+}
+
+fn statement(source: &str) -> StatementKind<'_> {
+    let mut body = parse(source).body;
+    assert_eq!(body.len(), 1, "{:?}", source);
+    body.remove(0).kind
+}
+
+fn expression(source: &str) -> ExpressionKind<'_> {
+    match statement(source) {
+        StatementKind::Expression(expression) => expression.kind,
+        kind => panic!("{:?} is not an expression statement: {:?}", source, kind)
+    }
+}
+
+fn error_with(source: &str, options: Options) -> ParseErrorKind {
+    match Parser::new(source, options).parse() {
+        Ok(program) => panic!("{:?} parsed as {:?}", source, program.body),
+        Err(e) => e.kind
+    }
+}
+
+fn error(source: &str) -> ParseErrorKind {
+    error_with(source, Options::default())
+}
+
+fn version(version: EcmaVersion) -> Options {
+    Options { version, ..Options::default() }
+}
+
+fn name<'a>(expression: &'a Expression) -> &'a str {
+    match expression.kind {
+        ExpressionKind::Identifier(ref name) => name,
+        ref kind => panic!("not an identifier: {:?}", kind)
+    }
+}
+
+#[test]
+fn binary_operator_precedence() {
+    let ExpressionKind::Binary { operator: BinaryOperator::Plus, left, right } = expression("a + b * c") else { panic!() };
+    assert_eq!(name(&left), "a");
+    assert!(matches!(right.kind, ExpressionKind::Binary { operator: BinaryOperator::Multiply, .. }));
+
+    let ExpressionKind::Binary { operator: BinaryOperator::Minus, left, .. } = expression("a - b - c") else { panic!() };
+    assert!(matches!(left.kind, ExpressionKind::Binary { operator: BinaryOperator::Minus, .. }));
+
+    // '**' is right-associative
+    let ExpressionKind::Binary { operator: BinaryOperator::Exponent, left, right } = expression("a ** b ** c") else { panic!() };
+    assert_eq!(name(&left), "a");
+    assert!(matches!(right.kind, ExpressionKind::Binary { operator: BinaryOperator::Exponent, .. }));
+
+    // a unary expression on the left of '**' must be parenthesized
+    let ExpressionKind::Binary { operator: BinaryOperator::Exponent, left, .. } = expression("(-x) ** 2") else { panic!() };
+    assert!(matches!(left.kind, ExpressionKind::Unary { operator: UnaryOperator::Minus, .. }));
+    assert!(matches!(expression("-(x ** 2)"), ExpressionKind::Unary { .. }));
+    assert!(matches!(expression("++x ** 2"), ExpressionKind::Binary { .. }));
+    assert_eq!(error("-x ** 2"), ParseErrorKind::UnaryBeforeExponent);
+    assert_eq!(error("a * typeof x ** 2"), ParseErrorKind::UnaryBeforeExponent);
+    assert_eq!(error("async function f() { await x ** 2 }"), ParseErrorKind::UnaryBeforeExponent);
+
+    let ExpressionKind::Logical { operator: LogicalOperator::Or, right, .. } = expression("a || b && c") else { panic!() };
+    assert!(matches!(right.kind, ExpressionKind::Logical { operator: LogicalOperator::And, .. }));
+
+    let ExpressionKind::Logical { operator: LogicalOperator::NullishCoalescing, left, .. } = expression("(a || b) ?? c ?? d") else { panic!() };
+    assert!(matches!(left.kind, ExpressionKind::Logical { operator: LogicalOperator::NullishCoalescing, .. }));
+    assert!(matches!(expression("a ?? b | c"), ExpressionKind::Logical { operator: LogicalOperator::NullishCoalescing, .. }));
+    for source in ["a ?? b || c", "a || b ?? c", "a ?? b && c", "a && b ?? c"] {
+        assert_eq!(error(source), ParseErrorKind::MixedCoalesce, "{}", source);
+    }
+
+    let ExpressionKind::Binary { operator: BinaryOperator::Instanceof, left, .. } = expression("a in b instanceof c") else { panic!() };
+    assert!(matches!(left.kind, ExpressionKind::Binary { operator: BinaryOperator::In, .. }));
+}
+
+#[test]
+fn unary_update_and_assignment() {
+    let ExpressionKind::Unary { operator: UnaryOperator::Typeof, argument } = expression("typeof -a") else { panic!() };
+    assert!(matches!(argument.kind, ExpressionKind::Unary { operator: UnaryOperator::Minus, .. }));
+    assert!(matches!(expression("a++"), ExpressionKind::Update { operator: UpdateOperator::Increment, prefix: false, .. }));
+    assert!(matches!(expression("--a.b"), ExpressionKind::Update { operator: UpdateOperator::Decrement, prefix: true, .. }));
+
+    let ExpressionKind::Assignment { operator: AssignmentOperator::Assign, right, .. } = expression("a = b += c") else { panic!() };
+    assert!(matches!(right.kind, ExpressionKind::Assignment { operator: AssignmentOperator::PlusAssign, .. }));
+
+    let ExpressionKind::Conditional { alternate, .. } = expression("a ? b : c = d") else { panic!() };
+    assert!(matches!(alternate.kind, ExpressionKind::Assignment { .. }));
+
+    assert!(matches!(expression("a, b, c"), ExpressionKind::Sequence(ref e) if e.len() == 3));
+    assert_eq!(error("1 = 2"), ParseErrorKind::AssigningToRvalue);
+    assert_eq!(error("a + b++ = c"), ParseErrorKind::AssigningToRvalue);
+    assert_eq!(error("++a()"), ParseErrorKind::AssigningToRvalue);
+}
+
+#[test]
+fn member_call_and_new() {
+    let ExpressionKind::Call { callee, arguments, optional: false } = expression("a.b[c](d, e)") else { panic!() };
+    assert_eq!(arguments.len(), 2);
+    let ExpressionKind::Member { object, computed: true, .. } = callee.kind else { panic!() };
+    assert!(matches!(object.kind, ExpressionKind::Member { computed: false, .. }));
+
+    // the arguments belong to `new`
+    let ExpressionKind::New { callee, arguments } = expression("new a.b(c)") else { panic!() };
+    assert_eq!(arguments.len(), 1);
+    assert!(matches!(callee.kind, ExpressionKind::Member { .. }));
+    let ExpressionKind::Call { callee, .. } = expression("new a()()") else { panic!() };
+    assert!(matches!(callee.kind, ExpressionKind::New { .. }));
+    assert!(matches!(expression("new a"), ExpressionKind::New { ref arguments, .. } if arguments.is_empty()));
+
+    let ExpressionKind::Chain(chain) = expression("a?.b.c()") else { panic!() };
+    assert!(matches!(chain.kind, ExpressionKind::Call { optional: false, .. }));
+    assert_eq!(error("new a?.b()"), ParseErrorKind::OptionalChainInNew);
+}
+
+#[test]
+fn literals() {
+    assert!(matches!(expression("null"), ExpressionKind::Literal(Literal::Null)));
+    assert!(matches!(expression("true"), ExpressionKind::Literal(Literal::Boolean(true))));
+    assert!(matches!(expression("0x10"), ExpressionKind::Literal(Literal::Number(n)) if n == 16.0));
+    assert!(matches!(expression("'a\\x62'"), ExpressionKind::Literal(Literal::String(ref s)) if s == "ab"));
+    assert!(matches!(expression("/a/g"), ExpressionKind::Literal(Literal::Regexp { ref pattern, ref flags }) if pattern == "a" && flags == "g"));
+    assert!(matches!(expression("[a, , b]"), ExpressionKind::Array(ref e) if e.len() == 3 && e[1].is_none()));
+    let ExpressionKind::Object(properties) = expression("({a: 1, 'b': 2, 3: c, get d() {}, set d(v) {}})") else { panic!() };
+    let kinds: Vec<_> = properties.iter().map(|p| match *p {
+        ObjectMember::Property(ref property) => property.kind,
+        ObjectMember::Spread(_) => panic!()
+    }).collect();
+    assert_eq!(kinds, [PropertyKind::Init, PropertyKind::Init, PropertyKind::Init, PropertyKind::Get, PropertyKind::Set]);
+    assert_eq!(error("({get a(b) {}})"), ParseErrorKind::InvalidAccessorArity);
+}
+
+#[test]
+fn object_methods() {
+    let ExpressionKind::Object(properties) = expression("({a() {}, *b() {}, async c() {}, async *d() {}, [e]() {}, get() {}, async: 1})") else { panic!() };
+    let methods: Vec<_> = properties.iter().map(|p| match *p {
+        ObjectMember::Property(Property { method, value: Expression { kind: ExpressionKind::Function(ref f), .. }, .. }) => (method, f.generator, f.is_async),
+        ObjectMember::Property(ref property) => (property.method, false, false),
+        ObjectMember::Spread(_) => panic!()
+    }).collect();
+    assert_eq!(methods, [(true, false, false), (true, true, false), (true, false, true), (true, true, true), (true, false, false), (true, false, false), (false, false, false)]);
+    parse("({a() { super.b }})");
+    assert_eq!(error("({a(b, b) {}})"), ParseErrorKind::DuplicateParameter);
+    assert_eq!(error("({a() {}} = b)"), ParseErrorKind::AssigningToRvalue);
+    assert_eq!(error("({*a: 1})"), ParseErrorKind::UnexpectedToken);
+    assert_eq!(error("({async\na() {}})"), ParseErrorKind::UnexpectedToken);
+    assert_eq!(error_with("({a() {}})", version(EcmaVersion::Ecma5)), ParseErrorKind::FeatureRequiresVersion { feature: "Method definition", version: EcmaVersion::Ecma6 });
+    assert_eq!(error_with("({async a() {}})", version(EcmaVersion::Ecma2016)), ParseErrorKind::FeatureRequiresVersion { feature: "Async function", version: EcmaVersion::Ecma2017 });
+}
+
+#[test]
+fn spread_elements() {
+    let ExpressionKind::Array(elements) = expression("[a, ...b, .../c/]") else { panic!() };
+    assert!(matches!(elements[1], Some(Expression { kind: ExpressionKind::Spread(_), .. })));
+    assert!(matches!(elements[2], Some(Expression { kind: ExpressionKind::Spread(_), .. })));
+    let ExpressionKind::Call { arguments, .. } = expression("f(...a, b)") else { panic!() };
+    assert!(matches!(arguments[0].kind, ExpressionKind::Spread(_)));
+    let ExpressionKind::New { arguments, .. } = expression("new F(...a)") else { panic!() };
+    assert!(matches!(arguments[0].kind, ExpressionKind::Spread(_)));
+    let ExpressionKind::Object(members) = expression("({...a, b: 1, ...c})") else { panic!() };
+    assert!(matches!(members[..], [ObjectMember::Spread(_), ObjectMember::Property(_), ObjectMember::Spread(_)]));
+    assert_eq!(error("(...a)"), ParseErrorKind::UnexpectedToken);
+    assert_eq!(error("a + ...b"), ParseErrorKind::UnexpectedToken);
+}
+
+fn bound_names<'a>(pattern: &'a Pattern) -> Vec<&'a str> {
+    pattern.bound_names().into_iter().map(|(name, _)| name).collect()
+}
+
+#[test]
+fn destructuring() {
+    let StatementKind::VariableDeclaration(declaration) = statement("let [a, , [b] = c, ...d] = e, {f, g: h = 1, ['i']: j, ...k} = l;") else { panic!() };
+    let PatternKind::Array(ref elements) = declaration.declarations[0].id.kind else { panic!() };
+    assert!(elements[1].is_none());
+    assert!(matches!(elements[2], Some(Pattern { kind: PatternKind::Assignment { .. }, .. })));
+    assert!(matches!(elements[3], Some(Pattern { kind: PatternKind::Rest(_), .. })));
+    assert_eq!(bound_names(&declaration.declarations[0].id), ["a", "b", "d"]);
+    let PatternKind::Object(ref members) = declaration.declarations[1].id.kind else { panic!() };
+    assert!(matches!(members[0], ObjectPatternMember::Property(PatternProperty { shorthand: true, .. })));
+    assert!(matches!(members[2], ObjectPatternMember::Property(PatternProperty { computed: true, .. })));
+    assert!(matches!(members[3], ObjectPatternMember::Rest(_)));
+    assert_eq!(bound_names(&declaration.declarations[1].id), ["f", "h", "j", "k"]);
+
+    let StatementKind::FunctionDeclaration(function) = statement("function f([a], {b} = {}, ...c) {}") else { panic!() };
+    let names: Vec<_> = function.params.iter().flat_map(bound_names).collect();
+    assert_eq!(names, ["a", "b", "c"]);
+    assert!(matches!(statement("try {} catch ({message}) {}"), StatementKind::Try { handler: Some(CatchClause { param: Some(_), .. }), .. }));
+    assert!(matches!(statement("for (const [k, v] of m);"), StatementKind::ForOf { .. }));
+
+    // array and object literals on the left of `=` are converted
+    let ExpressionKind::Assignment { left, .. } = expression("[a.b, {c = 1, d: [e], ...f}] = g") else { panic!() };
+    let PatternKind::Array(ref elements) = left.kind else { panic!() };
+    assert!(matches!(elements[0], Some(Pattern { kind: PatternKind::Member(_), .. })));
+    let Some(Pattern { kind: PatternKind::Object(ref members), .. }) = elements[1] else { panic!() };
+    assert!(matches!(members[0], ObjectPatternMember::Property(PatternProperty { value: Pattern { kind: PatternKind::Assignment { .. }, .. }, .. })));
+    assert!(matches!(statement("for ([a, b] of c);"), StatementKind::ForOf { left: ForInit::Pattern(_), .. }));
+    assert!(matches!(expression("({a, b: c} = d)"), ExpressionKind::Assignment { .. }));
+    parse("[[...a,].b] = c");
+
+    assert_eq!(error("({a = 1})"), ParseErrorKind::ShorthandPropertyAssign);
+    assert_eq!(error("f({a = 1})"), ParseErrorKind::ShorthandPropertyAssign);
+    assert_eq!(error("[...a, b] = c"), ParseErrorKind::CommaAfterRestElement);
+    assert_eq!(error("let [...a,] = b"), ParseErrorKind::CommaAfterRestElement);
+    assert_eq!(error("[(a, b)] = c"), ParseErrorKind::AssigningToRvalue);
+    assert_eq!(error("({a}) = b"), ParseErrorKind::AssigningToRvalue);
+    assert_eq!(error("[a + 1] = b"), ParseErrorKind::AssigningToRvalue);
+    assert_eq!(error("[a] += b"), ParseErrorKind::AssigningToRvalue);
+    assert_eq!(error("({...{a}} = b)"), ParseErrorKind::UnexpectedToken);
+    assert_eq!(error("let {...[a]} = b"), ParseErrorKind::UnexpectedToken);
+    assert_eq!(error("let [a];"), ParseErrorKind::MissingPatternInitializer);
+    assert_eq!(error("for (let [a] = b in c);"), ParseErrorKind::ForLoopInitializer("for-in"));
+    assert_eq!(error("let {let} = a"), ParseErrorKind::LetInLexicalBinding);
+    assert_eq!(error("function f([a], {a}) { 'use strict' }"), ParseErrorKind::DuplicateParameter);
+    assert_eq!(error("'use strict'; [eval] = a"), ParseErrorKind::StrictModeBinding("eval"));
+    assert_eq!(error("function* g() { var {yield} = a }"), ParseErrorKind::YieldInGenerator);
+}
+
+#[test]
+fn templates() {
+    let ExpressionKind::Template(template) = expression("`a${b}c${d + 1}`") else { panic!() };
+    assert_eq!(template.expressions.len(), 2);
+    let quasis: Vec<_> = template.quasis.iter().map(|q| (q.cooked.as_str(), q.tail)).collect();
+    assert_eq!(quasis, [("a", false), ("c", false), ("", true)]);
+
+    let ExpressionKind::Template(template) = expression("`\\x41${`${a}`}`") else { panic!() };
+    assert_eq!((template.quasis[0].cooked.as_str(), template.quasis[0].raw.as_str()), ("A", "\\x41"));
+    assert!(matches!(template.expressions[0].kind, ExpressionKind::Template(_)));
+
+    let ExpressionKind::TaggedTemplate { tag, quasi } = expression("a.b`c`") else { panic!() };
+    assert!(matches!(tag.kind, ExpressionKind::Member { .. }));
+    assert_eq!(quasi.quasis.len(), 1);
+    assert!(matches!(expression("new a`b`"), ExpressionKind::New { .. }));
+    // a '/' after a template divides
+    assert!(matches!(expression("`a` / b / c"), ExpressionKind::Binary { .. }));
+    assert_eq!(error("a?.b`c`"), ParseErrorKind::OptionalChainInTaggedTemplate);
+    assert_eq!(error("`a${}`"), ParseErrorKind::UnexpectedToken);
+}
+
+#[test]
+fn statements() {
+    let program = parse("var a = 1, b; if (a) b; else { c }\nfor (;;) break; debugger;");
+    assert_eq!(program.body.len(), 4);
+    let StatementKind::VariableDeclaration(ref declaration) = program.body[0].kind else { panic!() };
+    assert_eq!(declaration.declarations.len(), 2);
+    assert!(matches!(program.body[1].kind, StatementKind::If { alternate: Some(_), .. }));
+    assert!(matches!(program.body[2].kind, StatementKind::For { init: None, test: None, update: None, .. }));
+    assert!(matches!(program.body[3].kind, StatementKind::Debugger));
+
+    assert!(matches!(statement("for (var a in b);"), StatementKind::ForIn { left: ForInit::VariableDeclaration(_), .. }));
+    assert!(matches!(statement("for (a.b in c);"), StatementKind::ForIn { left: ForInit::Pattern(Pattern { kind: PatternKind::Member(_), .. }), .. }));
+    assert!(matches!(statement("for (let a of b);"), StatementKind::ForOf { is_await: false, .. }));
+    let StatementKind::FunctionDeclaration(function) = statement("async function f() { for await (a of b); }") else { panic!() };
+    assert!(matches!(function.body.body[0].kind, StatementKind::ForOf { is_await: true, .. }));
+    assert_eq!(error("async function f() { for await (a in b); }"), ParseErrorKind::UnexpectedToken);
+    assert_eq!(error("async function f() { for await (;;); }"), ParseErrorKind::UnexpectedToken);
+    assert_eq!(error("function f() { for await (a of b); }"), ParseErrorKind::UnexpectedToken);
+    assert_eq!(error("for await (a of b);"), ParseErrorKind::UnexpectedToken);
+    assert!(matches!(statement("for (var a = (b in c); a; a++);"), StatementKind::For { init: Some(_), .. }));
+    assert!(matches!(statement("while (a) a--"), StatementKind::While { .. }));
+    assert!(matches!(statement("do a++; while (a)"), StatementKind::DoWhile { .. }));
+    assert!(matches!(statement("with (a) b"), StatementKind::With { .. }));
+    assert!(matches!(statement("try {} catch (e) {} finally {}"), StatementKind::Try { handler: Some(_), finalizer: Some(_), .. }));
+    assert!(matches!(statement("try {} catch {}"), StatementKind::Try { handler: Some(CatchClause { param: None, .. }), .. }));
+    assert!(matches!(statement("throw a"), StatementKind::Throw(_)));
+    assert!(matches!(statement(";"), StatementKind::Empty));
+
+    let StatementKind::Switch { cases, .. } = statement("switch (a) { case 1: b; break; default: case 2: }") else { panic!() };
+    let consequents: Vec<_> = cases.iter().map(|c| (c.test.is_some(), c.consequent.len())).collect();
+    assert_eq!(consequents, [(true, 2), (false, 0), (true, 0)]);
+}
+
+#[test]
+fn functions() {
+    let StatementKind::FunctionDeclaration(function) = statement("function f(a, b) { return a }") else { panic!() };
+    assert_eq!(function.id.unwrap().name, "f");
+    assert_eq!(function.params.len(), 2);
+    assert!(matches!(function.body.body[0].kind, StatementKind::Return(Some(_))));
+
+    let ExpressionKind::Function(function) = expression("(function* () { yield; yield* a })") else { panic!() };
+    assert!(function.generator && function.id.is_none());
+    assert!(matches!(statement("async function f() { await a }"), StatementKind::FunctionDeclaration(Function { is_async: true, .. })));
+    // the lookahead after `async` skips the same whitespace as the tokenizer
+    assert!(matches!(statement("async\u{feff}function f() {}"), StatementKind::FunctionDeclaration(Function { is_async: true, .. })));
+    assert_eq!(error("function* g() { var yield }"), ParseErrorKind::YieldInGenerator);
+    assert_eq!(error("return"), ParseErrorKind::ReturnOutsideFunction);
+    assert_eq!(error("function f() { 'use strict'; with (a) {} }"), ParseErrorKind::WithInStrictMode);
+    assert_eq!(error("function f(a, a) { 'use strict' }"), ParseErrorKind::DuplicateParameter);
+    assert_eq!(error("function eval() { 'use strict' }"), ParseErrorKind::StrictModeBinding("eval"));
+}
+
+#[test]
+fn arrow_functions() {
+    let ExpressionKind::ArrowFunction(arrow) = expression("(a, [b], {c} = {}, ...d) => a") else { panic!() };
+    let names: Vec<_> = arrow.params.iter().flat_map(bound_names).collect();
+    assert_eq!(names, ["a", "b", "c", "d"]);
+    assert!(matches!(arrow.body, ArrowBody::Expression(_)) && !arrow.is_async);
+    assert!(matches!(expression("a => {}"), ExpressionKind::ArrowFunction(ArrowFunction { body: ArrowBody::Block(_), .. })));
+    assert!(matches!(expression("() => ({})"), ExpressionKind::ArrowFunction(ArrowFunction { body: ArrowBody::Expression(_), .. })));
+    assert!(matches!(expression("async a => a"), ExpressionKind::ArrowFunction(ArrowFunction { is_async: true, .. })));
+    assert!(matches!(expression("async (a) => await a"), ExpressionKind::ArrowFunction(ArrowFunction { is_async: true, .. })));
+    // without `=>` these are a call and a name
+    assert!(matches!(expression("async(a, ...b)"), ExpressionKind::Call { .. }));
+    assert_eq!(parse("async\na => a").body.len(), 2);
+    assert!(matches!(expression("x = a => b"), ExpressionKind::Assignment { .. }));
+    assert!(matches!(expression("(a => b)(c)"), ExpressionKind::Call { .. }));
+    assert!(matches!(expression("(a, b)"), ExpressionKind::Sequence(_)));
+    parse("function f() { return () => new.target }");
+    parse("function* g() { () => yield }");
+
+    assert_eq!(error("a\n=> b"), ParseErrorKind::UnexpectedToken);
+    assert_eq!(error("(a)\n=> b"), ParseErrorKind::UnexpectedToken);
+    assert_eq!(error("a => {}(b)"), ParseErrorKind::UnexpectedToken);
+    assert_eq!(error("a => {} + b"), ParseErrorKind::UnexpectedToken);
+    assert_eq!(error("a + b => c"), ParseErrorKind::UnexpectedToken);
+    assert_eq!(error("()"), ParseErrorKind::UnexpectedToken);
+    assert_eq!(error("(a,)"), ParseErrorKind::UnexpectedToken);
+    assert_eq!(error("(...a, b) => c"), ParseErrorKind::CommaAfterRestElement);
+    assert_eq!(error("async (...a, b) => c"), ParseErrorKind::CommaAfterRestElement);
+    assert_eq!(error("((a)) => b"), ParseErrorKind::ParenthesizedPattern);
+    assert_eq!(error("({a: (b)}) => c"), ParseErrorKind::ParenthesizedPattern);
+    assert_eq!(error("(a.b) => c"), ParseErrorKind::BindingMemberExpression);
+    assert_eq!(error("([a.b]) => c"), ParseErrorKind::BindingMemberExpression);
+    assert_eq!(error("(a, a) => b"), ParseErrorKind::DuplicateParameter);
+    assert_eq!(error("'use strict'; (eval) => a"), ParseErrorKind::StrictModeBinding("eval"));
+    assert_eq!(error("async (await) => a"), ParseErrorKind::AwaitInAsyncFunction);
+    assert_eq!(error("async await => a"), ParseErrorKind::AwaitInAsyncFunction);
+    assert_eq!(error("() => new.target"), ParseErrorKind::NewTargetOutsideFunction);
+    // the body of an arrow in a `for` header can't contain `in`
+    assert_eq!(error("for (a => b in c;;);"), ParseErrorKind::AssigningToRvalue);
+    parse("for ((a => b in c);;);");
+}
+
+#[test]
+fn classes() {
+    let StatementKind::ClassDeclaration(class) = statement("class A extends B { constructor() { super() } static get a() {} b = 1; static; static {} }") else { panic!() };
+    assert_eq!(class.id.unwrap().name, "A");
+    assert!(class.super_class.is_some());
+    let kinds: Vec<_> = class.body.body.iter().map(|member| match *member {
+        ClassMember::Method(ref method) => (method.kind.as_str(), method.is_static),
+        ClassMember::Property(ref property) => (name(&property.key), property.is_static),
+        ClassMember::StaticBlock(_) => ("{}", true)
+    }).collect();
+    assert_eq!(kinds, [("constructor", false), ("get", true), ("b", false), ("static", false), ("{}", true)]);
+    assert!(matches!(expression("(class {})"), ExpressionKind::Class(Class { id: None, .. })));
+    // a class body is strict
+    assert_eq!(error("class A { a() { with (b) {} } }"), ParseErrorKind::WithInStrictMode);
+    parse("class A {} with (b) {}");
+    // a '/' after a class declaration starts a regexp
+    assert!(is_regexp_call(&parse("class A {}\n/re/.test(x)").body[1]));
+
+    assert_eq!(error("class A { constructor() {} constructor() {} }"), ParseErrorKind::DuplicateConstructor);
+    assert_eq!(error("class A { *constructor() {} }"), ParseErrorKind::InvalidConstructor("be a generator"));
+    assert_eq!(error("class A { constructor = 1 }"), ParseErrorKind::FieldNamedConstructor);
+    assert_eq!(error("class A { static prototype() {} }"), ParseErrorKind::StaticPrototype);
+    assert_eq!(error("class A { set a(...b) {} }"), ParseErrorKind::InvalidAccessorArity);
+    assert_eq!(error("class A { constructor() { super() } }"), ParseErrorKind::SuperCallOutsideConstructor);
+    assert_eq!(error("class A extends B { a() { super() } }"), ParseErrorKind::SuperCallOutsideConstructor);
+    assert_eq!(error("class A { a() { function f() { super.a } } }"), ParseErrorKind::SuperOutsideMethod);
+    assert_eq!(error("super.a"), ParseErrorKind::SuperOutsideMethod);
+    parse("({ get a() { return super.a } })");
+}
+
+#[test]
+fn private_names() {
+    let StatementKind::ClassDeclaration(class) = statement("class A { #a = 1; get #b() {} set #b(v) {} c(o) { return #a in o && this.#b } }") else { panic!() };
+    let ClassMember::Property(ref field) = class.body.body[0] else { panic!() };
+    assert!(matches!(field.key.kind, ExpressionKind::PrivateIdentifier(ref name) if name == "a"));
+    let ClassMember::Method(ref method) = class.body.body[3] else { panic!() };
+    let StatementKind::Return(Some(ref argument)) = method.value.body.body[0].kind else { panic!() };
+    let ExpressionKind::Logical { ref left, ref right, .. } = argument.kind else { panic!() };
+    assert!(matches!(left.kind, ExpressionKind::Binary { operator: BinaryOperator::In, ref left, .. } if matches!(left.kind, ExpressionKind::PrivateIdentifier(_))));
+    assert!(matches!(right.kind, ExpressionKind::Member { ref property, .. } if matches!(property.kind, ExpressionKind::PrivateIdentifier(_))));
+    // names can be used before their declaration and in nested classes
+    parse("class A { a() { return class { b() { return this.#c } } } #c }");
+
+    assert_eq!(error("this.#a"), ParseErrorKind::UndeclaredPrivateName);
+    assert_eq!(error("class A { a() { this.#b } }"), ParseErrorKind::UndeclaredPrivateName);
+    assert_eq!(error("class A { #a; #a }"), ParseErrorKind::DuplicatePrivateName);
+    assert_eq!(error("class A { get #a() {} static set #a(v) {} }"), ParseErrorKind::DuplicatePrivateName);
+    assert_eq!(error("class A { #constructor() {} }"), ParseErrorKind::PrivateNameConstructor);
+    assert_eq!(error("class A { #a; b() { delete this.#a } }"), ParseErrorKind::PrivateFieldDelete);
+    assert_eq!(error("class A { #a; b() { 1 + #a in c } }"), ParseErrorKind::PrivateNameNotLeft);
+    assert_eq!(error("class A { #a; b() { #a } }"), ParseErrorKind::UnexpectedToken);
+    assert_eq!(error("class A extends B { #a; b() { super.#a } }"), ParseErrorKind::UnexpectedToken);
+    assert_eq!(error("({#a: 1})"), ParseErrorKind::UnexpectedToken);
+}
+
+#[test]
+fn static_blocks() {
+    let StatementKind::ClassDeclaration(class) = statement("class A { static { this.a = 1; super.b; new.target } }") else { panic!() };
+    let ClassMember::StaticBlock(ref block) = class.body.body[0] else { panic!() };
+    assert_eq!((block.start, block.body.len()), (10, 3));
+    parse("class A { static { function f() { return arguments } } }");
+    parse("x: { class A { static { x: for (;;) break x } } }");
+    assert_eq!(error("class A { static { return } }"), ParseErrorKind::ReturnOutsideFunction);
+    assert_eq!(error("class A { static { arguments } }"), ParseErrorKind::InvalidInStaticBlock("arguments"));
+    assert_eq!(error("class A { static { () => await } }"), ParseErrorKind::InvalidInStaticBlock("await"));
+    assert_eq!(error("x: { class A { static { break x } } }"), ParseErrorKind::UnsyntacticBreak);
+    let module = Options { source_type: SourceType::Module, ..Options::default() };
+    assert_eq!(error_with("class A { static { await a } }", module), ParseErrorKind::InvalidInStaticBlock("await"));
+}
+
+#[test]
+fn labels_and_jumps() {
+    let StatementKind::Labeled { label, body } = statement("a: for (;;) continue a") else { panic!() };
+    assert_eq!(label.name, "a");
+    assert!(matches!(body.kind, StatementKind::For { .. }));
+    assert!(matches!(statement("a: { break a }"), StatementKind::Labeled { .. }));
+    assert_eq!(error("break"), ParseErrorKind::UnsyntacticBreak);
+    assert_eq!(error("a: { continue a }"), ParseErrorKind::UnsyntacticContinue);
+    assert_eq!(error("switch (a) { case 1: continue }"), ParseErrorKind::UnsyntacticContinue);
+    assert_eq!(error("for (;;) break b"), ParseErrorKind::UnsyntacticBreak);
+    assert_eq!(error("a: a: ;"), ParseErrorKind::LabelAlreadyDeclared);
+    assert_eq!(error("(a): b"), ParseErrorKind::UnexpectedToken);
+}
+
+#[test]
+fn loop_bodies() {
+    assert_eq!(error("while (a) function f() {}"), ParseErrorKind::UnexpectedToken);
+    assert_eq!(error("for (;;) class C {}"), ParseErrorKind::UnexpectedToken);
+    assert_eq!(error("for (a of b) async function f() {}"), ParseErrorKind::UnexpectedToken);
+    assert_eq!(error("while (a) const b = 1"), ParseErrorKind::UnexpectedToken);
+    assert_eq!(error("do let [a] = b; while (0)"), ParseErrorKind::UnexpectedToken);
+    assert_eq!(error("while (a) let b"), ParseErrorKind::UnexpectedToken);
+    assert!(matches!(statement("while (a) var b"), StatementKind::While { .. }));
+    assert_eq!(parse("while (a) let\nb = 1").body.len(), 2);
+    assert_eq!(parse("while (a) async\nfunction f() {}").body.len(), 2);
+    parse_with("while (a) function f() {}", version(EcmaVersion::Ecma5));
+}
+
+#[test]
+fn automatic_semicolon_insertion() {
+    assert_eq!(parse("a\nb").body.len(), 2);
+    assert_eq!(parse("a\n++b").body.len(), 2);
+    assert_eq!(parse("{ a } b").body.len(), 2);
+    let StatementKind::FunctionDeclaration(function) = statement("function f() { return\na }") else { panic!() };
+    assert!(matches!(function.body.body[..], [Statement { kind: StatementKind::Return(None), .. }, _]));
+    assert_eq!(error("a b"), ParseErrorKind::UnexpectedToken);
+    assert_eq!(error("throw\na"), ParseErrorKind::IllegalNewlineAfterThrow);
+}
+
+// `/re/.test(a)`, which only parses if the tokenizer read a regexp
+fn is_regexp_call(statement: &Statement) -> bool {
+    let StatementKind::Expression(Expression { kind: ExpressionKind::Call { ref callee, .. }, .. }) = statement.kind else { return false };
+    let ExpressionKind::Member { ref object, .. } = callee.kind else { return false };
+    matches!(object.kind, ExpressionKind::Literal(Literal::Regexp { .. }))
+}
+
+#[test]
+fn regexp_after_statement_headers() {
+    let StatementKind::If { consequent, .. } = statement("if (a) /b/.test(c)") else { panic!() };
+    assert!(is_regexp_call(&consequent));
+    let StatementKind::While { body, .. } = statement("while (a) /b/g.exec(c)") else { panic!() };
+    assert!(is_regexp_call(&body));
+    let StatementKind::For { body, .. } = statement("for (;;) /b/.test(c)") else { panic!() };
+    assert!(is_regexp_call(&body));
+    let StatementKind::ForOf { body, .. } = statement("for (a of b) /b/.test(c)") else { panic!() };
+    assert!(is_regexp_call(&body));
+    let StatementKind::With { body, .. } = statement("with (a) /b/.test(c)") else { panic!() };
+    assert!(is_regexp_call(&body));
+    let program = parse("do ; while (a) /b/.test(c)");
+    assert!(is_regexp_call(&program.body[1]));
+    // an expression in parentheses is still divided
+    assert!(matches!(expression("(a) / b / c"), ExpressionKind::Binary { .. }));
+}
+
+#[test]
+fn regexp_after_closing_brace() {
+    for source in ["{}\n/re/.test(x)", "function f() {}\n/re/.test(x)", "switch (a) {}\n/re/.test(x)", "try {} catch (e) {}\n/re/.test(x)"] {
+        let program = parse(source);
+        assert!(is_regexp_call(&program.body[1]), "{:?}", source);
+    }
+    let options = Options { source_type: SourceType::Module, ..Options::default() };
+    let program = parse_with("export default function () {}\n/re/.test(x)", options);
+    assert!(is_regexp_call(&program.body[1]));
+    // the `}` of a function expression or object is followed by an operator
+    let ExpressionKind::Assignment { right, .. } = expression("x = function () {} / 2") else { panic!() };
+    assert!(matches!(right.kind, ExpressionKind::Binary { .. }));
+    assert!(matches!(expression("({} / 2)"), ExpressionKind::Binary { .. }));
+}
+
+#[test]
+fn syntax_errors() {
+    assert_eq!(error("try {}"), ParseErrorKind::MissingCatchOrFinally);
+    assert_eq!(error("switch (a) { default: default: }"), ParseErrorKind::MultipleDefaultClauses);
+    assert_eq!(error("'use strict'; with (a) {}"), ParseErrorKind::WithInStrictMode);
+    assert_eq!(error("for (let a = 1 of b);"), ParseErrorKind::ForLoopInitializer("for-of"));
+    assert_eq!(error("const a;"), ParseErrorKind::UnexpectedToken);
+    assert_eq!(error("let let = 1"), ParseErrorKind::LetInLexicalBinding);
+    assert_eq!(error("new.target"), ParseErrorKind::NewTargetOutsideFunction);
+    assert_eq!(error("import.meta"), ParseErrorKind::ImportMetaOutsideModule);
+    assert_eq!(error("import a from 'a'"), ParseErrorKind::ImportExportOutsideModule);
+    assert_eq!(error("var enum"), ParseErrorKind::ReservedWord("enum"));
+    // a directive applies to the code before it
+    assert_eq!(error("'\\01'; 'use strict'"), ParseErrorKind::OctalInStrictMode);
+}
+
+#[test]
+fn reserved_words() {
+    assert_eq!(error("'use strict'; var let"), ParseErrorKind::StrictModeReservedWord("let"));
+    assert_eq!(error("function f() { 'use strict'; static }"), ParseErrorKind::StrictModeReservedWord("static"));
+    assert_eq!(error("'use strict'; arguments = 1"), ParseErrorKind::StrictModeBinding("arguments"));
+    assert_eq!(error_with("var abstract", version(EcmaVersion::Ecma3)), ParseErrorKind::ReservedWord("abstract"));
+    parse_with("var abstract; var let; a.enum", version(EcmaVersion::Ecma5));
+}
+
+#[test]
+fn escaped_keywords() {
+    assert_eq!(error("var \\u0076ar = 1;"), ParseErrorKind::EscapedKeyword);
+    assert_eq!(error("var \\u006eew;"), ParseErrorKind::EscapedKeyword);
+    assert_eq!(error("x = \\u0074rue;"), ParseErrorKind::EscapedKeyword);
+    assert_eq!(error("x \\u0069n y"), ParseErrorKind::EscapedKeyword);
+    assert_eq!(error("({\\u0076ar})"), ParseErrorKind::EscapedKeyword);
+    // contextual keywords used as keywords
+    assert_eq!(error("l\\u0065t x = 1"), ParseErrorKind::EscapedKeyword);
+    assert_eq!(error("\\u0061sync function f() {}"), ParseErrorKind::EscapedKeyword);
+    assert_eq!(error("\\u0061sync x => x"), ParseErrorKind::EscapedKeyword);
+    assert_eq!(error("for (x \\u006ff y);"), ParseErrorKind::EscapedKeyword);
+    assert_eq!(error("function f() { new.t\\u0061rget }"), ParseErrorKind::EscapedKeyword);
+    assert_eq!(error("class A { st\\u0061tic m() {} }"), ParseErrorKind::EscapedKeyword);
+    assert_eq!(error("({ g\\u0065t x() {} })"), ParseErrorKind::EscapedKeyword);
+    let module = Options { source_type: SourceType::Module, ..Options::default() };
+    assert_eq!(error_with("import a fr\\u006fm 'a'", module), ParseErrorKind::EscapedKeyword);
+    // but they are still names
+    parse("a.\\u0076ar; ({\\u0076ar: 1}); l\\u0065t\nx = 1; l\\u0065t[0] = 1; \\u0061sync(x)");
+    parse("class A { st\\u0061tic() {} } ({ g\\u0065t: 1 })");
+}
+
+#[test]
+fn version_gated_syntax() {
+    let requires = |feature, version| ParseErrorKind::FeatureRequiresVersion { feature, version };
+    assert_eq!(error_with("a ** b", version(EcmaVersion::Ecma6)), requires("'**'", EcmaVersion::Ecma2016));
+    assert_eq!(error_with("async function f() {}", version(EcmaVersion::Ecma2016)), requires("Async function", EcmaVersion::Ecma2017));
+    assert_eq!(error_with("async a => a", version(EcmaVersion::Ecma2016)), requires("Async function", EcmaVersion::Ecma2017));
+    assert_eq!(error_with("async (a) => a", version(EcmaVersion::Ecma2016)), requires("Async function", EcmaVersion::Ecma2017));
+    assert_eq!(error_with("(a,) => a", version(EcmaVersion::Ecma2016)), requires("Trailing comma in parameters", EcmaVersion::Ecma2017));
+    assert_eq!(error_with("async function f() { for await (a of b); }", version(EcmaVersion::Ecma2017)), requires("for-await-of", EcmaVersion::Ecma2018));
+    assert_eq!(error_with("class A { #a }", version(EcmaVersion::Ecma2021)), requires("Private name", EcmaVersion::Ecma2022));
+    assert_eq!(error_with("class A { static {} }", version(EcmaVersion::Ecma2021)), requires("Class static block", EcmaVersion::Ecma2022));
+    parse_with("(a,) => a", version(EcmaVersion::Ecma2017));
+    // `=>` is only a token since ES6
+    assert_eq!(error_with("a => a", version(EcmaVersion::Ecma5)), ParseErrorKind::UnexpectedToken);
+    assert_eq!(error_with("try {} catch {}", version(EcmaVersion::Ecma2018)), requires("Optional catch binding", EcmaVersion::Ecma2019));
+    assert_eq!(error_with("a?.b", version(EcmaVersion::Ecma2019)), requires("Optional chaining", EcmaVersion::Ecma2020));
+    assert_eq!(error_with("a ?? b", version(EcmaVersion::Ecma2019)), requires("'??'", EcmaVersion::Ecma2020));
+    assert_eq!(error_with("a ||= b", version(EcmaVersion::Ecma2020)), requires("Logical assignment", EcmaVersion::Ecma2021));
+    assert_eq!(error_with("({a: 1,})", version(EcmaVersion::Ecma3)), requires("Trailing comma in object literal", EcmaVersion::Ecma5));
+    assert_eq!(error_with("[a,]", version(EcmaVersion::Ecma3)), requires("Trailing comma in array literal", EcmaVersion::Ecma5));
+    assert_eq!(error_with("[a, ,]", version(EcmaVersion::Ecma3)), requires("Trailing comma in array literal", EcmaVersion::Ecma5));
+    parse_with("[a, , b]", version(EcmaVersion::Ecma3));
+    parse_with("[a,]", version(EcmaVersion::Ecma5));
+    assert_eq!(error_with("({...a})", version(EcmaVersion::Ecma2017)), requires("Object rest/spread", EcmaVersion::Ecma2018));
+    parse_with("({...a})", version(EcmaVersion::Ecma2018));
+    assert_eq!(error_with("let {...a} = b", version(EcmaVersion::Ecma2017)), requires("Object rest/spread", EcmaVersion::Ecma2018));
+    assert_eq!(error_with("({...a} = b)", version(EcmaVersion::Ecma2017)), requires("Object rest/spread", EcmaVersion::Ecma2018));
+    parse_with("let {...a} = b", version(EcmaVersion::Ecma2018));
+    assert_eq!(error_with("class A { a = 1 }", version(EcmaVersion::Ecma2021)), requires("Class field", EcmaVersion::Ecma2022));
+    assert_eq!(error_with("class A { static a }", version(EcmaVersion::Ecma2021)), requires("Class field", EcmaVersion::Ecma2022));
+    parse_with("class A { a() {} static b() {} }", version(EcmaVersion::Ecma6));
+    parse_with("class A { a = 1; static b }", version(EcmaVersion::Ecma2022));
+    assert_eq!(error_with("var [a] = b", version(EcmaVersion::Ecma5)), requires("Destructuring", EcmaVersion::Ecma6));
+    assert_eq!(error_with("[a] = b", version(EcmaVersion::Ecma5)), requires("Destructuring", EcmaVersion::Ecma6));
+    assert_eq!(error_with("function f(a = 1) {}", version(EcmaVersion::Ecma5)), requires("Default value", EcmaVersion::Ecma6));
+    assert_eq!(error_with("({a})", version(EcmaVersion::Ecma5)), requires("Shorthand property", EcmaVersion::Ecma6));
+    assert_eq!(error_with("({[a]: b})", version(EcmaVersion::Ecma5)), requires("Computed property name", EcmaVersion::Ecma6));
+    parse_with("[...a]", version(EcmaVersion::Ecma6));
+    let module = |version| Options { version, source_type: SourceType::Module, ..Options::default() };
+    assert_eq!(error_with("await a", module(EcmaVersion::Ecma2021)), requires("Top-level await", EcmaVersion::Ecma2022));
+    parse_with("await a", module(EcmaVersion::Ecma2022));
+}
+
+#[test]
+fn ecma3() {
+    let es3 = || version(EcmaVersion::Ecma3);
+    let requires = |feature| ParseErrorKind::FeatureRequiresVersion { feature, version: EcmaVersion::Ecma5 };
+    assert_eq!(error_with("({get a() {}})", es3()), requires("Accessor property"));
+    assert_eq!(error_with("a.class", es3()), requires("Reserved word as property name"));
+    assert_eq!(error_with("var debugger", es3()), ParseErrorKind::ReservedWord("debugger"));
+    // ES3 has no strict mode
+    parse_with("'use strict'; with (a) {}", es3());
+}
+
+#[test]
+fn modules() {
+    let options = || Options { source_type: SourceType::Module, ..Options::default() };
+    let program = parse_with("import a, { b as c } from 'd'; export { a }; export default 1; export * from 'e'", options());
+    let StatementKind::Import { ref specifiers, .. } = program.body[0].kind else { panic!() };
+    assert_eq!(specifiers.len(), 2);
+    assert!(matches!(program.body[1].kind, StatementKind::ExportNamed { declaration: None, .. }));
+    assert!(matches!(program.body[2].kind, StatementKind::ExportDefault(ExportDefault::Expression(_))));
+    assert!(matches!(program.body[3].kind, StatementKind::ExportAll { exported: None, .. }));
+    let program = parse_with("export class A {} export default class {}", options());
+    assert!(matches!(program.body[0].kind, StatementKind::ExportNamed { declaration: Some(_), .. }));
+    assert!(matches!(program.body[1].kind, StatementKind::ExportDefault(ExportDefault::Class(Class { id: None, .. }))));
+    assert_eq!(error_with("{ export var a }", options()), ParseErrorKind::ImportExportNotTopLevel);
+    assert_eq!(error_with("var await", options()), ParseErrorKind::AwaitInModule);
+    assert_eq!(error_with("010", options()), ParseErrorKind::OctalInStrictMode);
+    let program = parse_with("for await (a of b);", options());
+    assert!(matches!(program.body[0].kind, StatementKind::ForOf { is_await: true, .. }));
+}
+
+#[test]
+fn error_locations() {
+    let error = Parser::new("a;\n  b c", Options::default()).parse().unwrap_err();
+    assert_eq!(error.kind, ParseErrorKind::UnexpectedToken);
+    assert_eq!(error.loc.start, Position { line: 2, column: 4 });
+}
+
+#[test]
+fn comments_are_attached() {
+    let program = parse_with("// a\nb; /* c */\n/* d */ e;", Options { collect_comments: true, ..Options::default() });
+    let texts = |comments: &[Comment]| -> Vec<String> { comments.iter().map(|c| c.text.to_string()).collect() };
+    assert_eq!(texts(&program.comments), [" a", " c ", " d "]);
+    assert_eq!(program.comments[0].kind, CommentKind::Line);
+    assert_eq!(program.comments[1].kind, CommentKind::Block);
+    assert_eq!(texts(&program.body[0].leading_comments), [" a"]);
+    assert_eq!(texts(&program.body[0].trailing_comments), [" c "]);
+    assert_eq!(texts(&program.body[1].leading_comments), [" d "]);
+    // nothing is collected by default
+    assert!(parse("// a\nb").comments.is_empty());
+}
+
+#[test]
+fn tolerant_mode_reports_multiple_errors() {
+    let source = "var a = ;\nb = 1;\nif (c { d }\ne @ f;\ng(";
+    let mut parser = Parser::new(source, Options { tolerant: true, ..Options::default() });
+    let program = parser.parse().unwrap();
+    let errors: Vec<_> = parser.errors().iter().map(|e| (e.kind, e.loc.start.line)).collect();
+    assert_eq!(errors, [
+        (ParseErrorKind::UnexpectedToken, 1),
+        (ParseErrorKind::UnexpectedToken, 3),
+        (ParseErrorKind::UnexpectedCharacter, 4),
+        (ParseErrorKind::UnexpectedToken, 5)
+    ]);
+    // the statements that parsed are kept
+    assert_eq!(program.body.len(), 1);
+    assert!(matches!(program.body[0].kind, StatementKind::Expression(_)));
+
+    // an error in a function body keeps the function
+    let mut parser = Parser::new("function f() { a b; c }\nd", Options { tolerant: true, ..Options::default() });
+    let program = parser.parse().unwrap();
+    assert_eq!(parser.errors().len(), 1);
+    assert_eq!(program.body.len(), 2);
+    let StatementKind::FunctionDeclaration(ref function) = program.body[0].kind else { panic!() };
+    assert_eq!(function.body.body.len(), 1);
+}
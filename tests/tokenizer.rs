@@ -0,0 +1,382 @@
+use tyrion::{Tokenizer, Token, TokenType, Options, EcmaVersion, SourceType, ParseErrorKind, CommentKind};
+use std::borrow::Cow;
+
+fn tokens(source: &str) -> Vec<Token<'_>> {
+    tokens_with(source, Options::default())
+}
+
+fn tokens_with(source: &str, options: Options) -> Vec<Token<'_>> {
+    match Tokenizer::new(source, options).tokenize_all() {
+        Ok(tokens) => tokens,
+        Err(errors) => panic!("{:?} failed to tokenize: {:?}", source, errors)
+    }
+}
+
+fn value(source: &str) -> String {
+    tokens(source)[0].value.as_deref().unwrap().to_string()
+}
+
+fn number(source: &str) -> f64 {
+    let tokens = tokens(source);
+    assert_eq!(tokens.len(), 1, "{:?}", source);
+    assert_eq!(tokens[0].token_type, TokenType::Num);
+    tokens[0].number.unwrap()
+}
+
+fn bigint(source: &str) -> String {
+    let tokens = tokens(source);
+    assert_eq!(tokens[0].token_type, TokenType::BigInt);
+    tokens[0].bigint.clone().unwrap().into_owned()
+}
+
+fn error_with(source: &str, options: Options) -> ParseErrorKind {
+    match Tokenizer::new(source, options).tokenize_all() {
+        Ok(tokens) => panic!("{:?} tokenized as {:?}", source, tokens),
+        Err(errors) => errors[0].kind
+    }
+}
+
+fn error(source: &str) -> ParseErrorKind {
+    error_with(source, Options::default())
+}
+
+// module code is strict
+fn strict_error(source: &str) -> ParseErrorKind {
+    error_with(source, Options { source_type: SourceType::Module, ..Options::default() })
+}
+
+fn version(version: EcmaVersion) -> Options {
+    Options { version, ..Options::default() }
+}
+
+fn requires(feature: &'static str, version: EcmaVersion) -> ParseErrorKind {
+    ParseErrorKind::FeatureRequiresVersion { feature, version }
+}
+
+#[test]
+fn operators() {
+    let operators = [
+        "+", "-", "*", "/", "%", "**", "++", "--", "<<", ">>", ">>>", "<", ">", "<=", ">=", "==", "!=",
+        "===", "!==", "&", "|", "^", "!", "~", "&&", "||", "??", "=", "+=", "-=", "*=", "/=", "%=",
+        "**=", "<<=", ">>=", ">>>=", "&=", "|=", "^=", "&&=", "||=", "??="
+    ];
+    for operator in operators {
+        let source = format!("a {} b", operator);
+        let tokens = tokens(&source);
+        assert_eq!(tokens.len(), 3, "{}", operator);
+        assert!(matches!(tokens[1].token_type, TokenType::Operator(_)), "{}", operator);
+        assert_eq!(tokens[1].value.as_deref(), Some(operator));
+    }
+    // '=>' and '?' are punctuation
+    assert!(matches!(tokens("a => b")[1].token_type, TokenType::Punc(_)));
+    assert!(matches!(tokens("a ? b : c")[1].token_type, TokenType::Punc(_)));
+    // the longest operator wins
+    let tokens = tokens("a+++b>>>=c");
+    let values: Vec<_> = tokens.iter().map(|t| t.value.as_deref()).collect();
+    assert_eq!(values, [Some("a"), Some("++"), Some("+"), Some("b"), Some(">>>="), Some("c")]);
+}
+
+#[test]
+fn leading_zero_numbers() {
+    assert_eq!(number("0"), 0.0);
+    assert_eq!(number("0.5"), 0.5);
+    assert_eq!(number("010"), 8.0);
+    assert_eq!(number("0777"), 511.0);
+    // an 8 or 9 makes it decimal
+    assert_eq!(number("089"), 89.0);
+    assert_eq!(number("08.5"), 8.5);
+    assert_eq!(number("0o17"), 15.0);
+    assert_eq!(number("0O17"), 15.0);
+    assert_eq!(number("0b101"), 5.0);
+    assert_eq!(number("0x1F"), 31.0);
+    assert_eq!(strict_error("010"), ParseErrorKind::OctalInStrictMode);
+    assert_eq!(strict_error("00"), ParseErrorKind::OctalInStrictMode);
+    assert_eq!(strict_error("089"), ParseErrorKind::LeadingZeroInStrictMode);
+    assert_eq!(error("0o8"), ParseErrorKind::InvalidNumber);
+    assert_eq!(error("0x"), ParseErrorKind::InvalidNumber);
+    assert_eq!(error("3in x"), ParseErrorKind::IdentifierDirectlyAfterNumber);
+    assert_eq!(error("1e"), ParseErrorKind::InvalidNumber);
+}
+
+#[test]
+fn number_values_round_to_nearest() {
+    assert_eq!(number("0x1fffffffffffff"), 9007199254740991.0);
+    // ties round to even
+    assert_eq!(number("0x20000000000001"), 9007199254740992.0);
+    assert_eq!(number("0x20000000000003"), 9007199254740996.0);
+    assert_eq!(number("0xffffffffffffffffff"), 4722366482869645213696.0);
+    assert_eq!(number("07777777777777777777"), 144115188075855872.0);
+    assert_eq!(number("9007199254740993"), 9007199254740992.0);
+    assert_eq!(number("0.1000000000000000055511151231257827"), 0.1);
+    assert_eq!(number("0.30000000000000004"), 0.1 + 0.2);
+    assert_eq!(number("1.7976931348623157e308"), f64::MAX);
+    assert_eq!(number("1e400"), f64::INFINITY);
+    assert_eq!(number(".5e-3"), 0.0005);
+}
+
+#[test]
+fn numeric_separators() {
+    assert_eq!(number("1_000_000"), 1e6);
+    assert_eq!(number("0xF_F"), 255.0);
+    assert_eq!(number("1_0.0_1e1_0"), 10.01e10);
+    assert_eq!(tokens("1_000")[0].raw.as_deref(), Some("1_000"));
+    for source in ["1__0", "1_", "0x_1", "1._5", "1_.5", "1e_1", "0_1", "08_9"] {
+        assert_eq!(error(source), ParseErrorKind::MisplacedNumericSeparator, "{}", source);
+    }
+    assert_eq!(error_with("1_0", version(EcmaVersion::Ecma2020)), requires("Numeric separators", EcmaVersion::Ecma2021));
+}
+
+#[test]
+fn bigint_literals() {
+    assert_eq!(bigint("123n"), "123");
+    assert_eq!(bigint("0n"), "0");
+    assert_eq!(bigint("1_000n"), "1000");
+    assert_eq!(bigint("0x1Fn"), "31");
+    assert_eq!(bigint("0b1010n"), "10");
+    assert_eq!(bigint("0o777n"), "511");
+    assert_eq!(bigint("0xFFFFFFFFFFFFFFFFFFFFn"), "1208925819614629174706175");
+    assert_eq!(tokens("0x1Fn")[0].raw.as_deref(), Some("0x1Fn"));
+    assert!(matches!(tokens("123n")[0].bigint, Some(Cow::Borrowed("123"))));
+    assert!(matches!(tokens("0x1Fn")[0].bigint, Some(Cow::Owned(_))));
+    for source in ["1.5n", "1e3n", "01n", ".5n"] {
+        assert_eq!(error(source), ParseErrorKind::InvalidBigInt, "{}", source);
+    }
+    assert_eq!(error("1na"), ParseErrorKind::IdentifierDirectlyAfterNumber);
+    assert_eq!(error_with("1n", version(EcmaVersion::Ecma2019)), requires("BigInt", EcmaVersion::Ecma2020));
+}
+
+#[test]
+fn astral_identifiers() {
+    // MATHEMATICAL BOLD CAPITAL A and DIGIT ZERO, CJK EXTENSION B
+    for source in ["\u{1D400}", "a\u{1D7CE}", "\u{20000}", "\\u{1D400}b"] {
+        let tokens = tokens(source);
+        assert_eq!(tokens.len(), 1, "{}", source);
+        assert_eq!(tokens[0].token_type, TokenType::Name, "{}", source);
+    }
+    assert_eq!(value("\\u{1D400}b"), "\u{1D400}b");
+    assert_eq!(error("\u{1D7CE}"), ParseErrorKind::UnexpectedCharacter);
+    assert_eq!(error("\u{1F600}"), ParseErrorKind::UnexpectedCharacter);
+    assert_eq!(error("\\u{1F600}"), ParseErrorKind::InvalidUnicodeEscape);
+    // surrogate pairs are not combined in identifiers
+    assert_eq!(error("\\uD835\\uDC00"), ParseErrorKind::InvalidUnicodeEscape);
+    assert_eq!(error("a\\x41"), ParseErrorKind::ExpectedUnicodeEscape);
+}
+
+#[test]
+fn whitespace() {
+    // vertical tab, byte order mark, em space and ideographic space
+    assert_eq!(tokens("a\u{b}\u{feff}b\u{2003}\u{3000}c").len(), 3);
+    // shift out and next line are not whitespace in ECMAScript
+    assert_eq!(error("a\u{e}"), ParseErrorKind::UnexpectedCharacter);
+    assert_eq!(error("a\u{85}"), ParseErrorKind::UnexpectedCharacter);
+}
+
+#[test]
+fn regexp_after_expression() {
+    let tokens = tokens("a / b / c; x = /b/gi");
+    assert!(matches!(tokens[1].token_type, TokenType::Operator(_)));
+    assert_eq!(tokens[3].value.as_deref(), Some("/"));
+    let regexp = tokens.last().unwrap();
+    assert_eq!(regexp.token_type, TokenType::Regexp);
+    let regex = regexp.regex.as_ref().unwrap();
+    assert_eq!((regex.pattern, regex.flags), ("b", "gi"));
+    assert_eq!(error("x = /[/"), ParseErrorKind::UnterminatedRegexp);
+    assert_eq!(error("x = /a/gg"), ParseErrorKind::InvalidRegexpFlag);
+    assert_eq!(error_with("/a/v", version(EcmaVersion::Ecma2023)), requires("Regexp flag 'v'", EcmaVersion::Ecma2024));
+}
+
+#[test]
+fn string_escapes() {
+    assert_eq!(value(r"'\n\r\t\b\v\f'"), "\n\r\t\u{8}\u{b}\u{c}");
+    assert_eq!(value(r#""\'\"\\\q""#), "'\"\\q");
+    assert_eq!(value(r"'\x41B\u{43}'"), "ABC");
+    assert_eq!(value(r"'\u{1F600}'"), "\u{1F600}");
+    // an escaped surrogate pair is a single char
+    assert_eq!(value(r"'\uD83D\uDE00'"), "\u{1F600}");
+    assert_eq!(value("'a\\\nb\\\r\nc'"), "abc");
+    assert_eq!(value(r"'\0'"), "\0");
+}
+
+#[test]
+fn bad_string_escapes() {
+    assert_eq!(error(r"'\x4'"), ParseErrorKind::BadEscapeSequence);
+    assert_eq!(error(r"'\u12'"), ParseErrorKind::InvalidUnicodeEscape);
+    assert_eq!(error(r"'\u{}'"), ParseErrorKind::InvalidUnicodeEscape);
+    assert_eq!(error(r"'\u{110000}'"), ParseErrorKind::CodePointOutOfBounds);
+    assert_eq!(error("'a\nb'"), ParseErrorKind::UnterminatedStringConstant);
+    assert_eq!(error("'abc"), ParseErrorKind::UnterminatedStringConstant);
+    assert_eq!(error_with(r"'\u{41}'", version(EcmaVersion::Ecma5)), ParseErrorKind::InvalidUnicodeEscape);
+    assert_eq!(error_with("'a\\\nb'", version(EcmaVersion::Ecma3)), requires("Line continuation", EcmaVersion::Ecma5));
+}
+
+#[test]
+fn line_separators_in_strings() {
+    assert_eq!(value("'a\u{2028}b\u{2029}c'"), "a\u{2028}b\u{2029}c");
+    let options = version(EcmaVersion::Ecma2019);
+    assert_eq!(tokens_with("'\u{2028}'", options)[0].value.as_deref(), Some("\u{2028}"));
+    assert_eq!(error_with("'\u{2028}'", version(EcmaVersion::Ecma2018)), ParseErrorKind::UnterminatedStringConstant);
+    assert_eq!(error_with("'\u{2029}'", version(EcmaVersion::Ecma5)), ParseErrorKind::UnterminatedStringConstant);
+    assert_eq!(error("'a\rb'"), ParseErrorKind::UnterminatedStringConstant);
+}
+
+#[test]
+fn octal_escapes() {
+    assert_eq!(value(r"'\101\7\08'"), "A\u{7}\u{0}8");
+    // at most 255
+    assert_eq!(value(r"'\400'"), " 0");
+    assert_eq!(value(r"'\8\9'"), "89");
+    assert_eq!(strict_error(r"'\101'"), ParseErrorKind::OctalInStrictMode);
+    assert_eq!(strict_error(r"'\00'"), ParseErrorKind::OctalInStrictMode);
+    assert_eq!(strict_error(r"'\8'"), ParseErrorKind::OctalInStrictMode);
+    // '\0' not followed by a digit is allowed
+    let options = Options { source_type: SourceType::Module, ..Options::default() };
+    assert_eq!(tokens_with(r"'\0'", options)[0].value.as_deref(), Some("\0"));
+}
+
+#[test]
+fn template_tokens() {
+    let source = "`a${b}c`";
+    let tokens = tokens(source);
+    let types: Vec<_> = tokens.iter().map(|t| (t.token_type, t.value.as_deref())).collect();
+    assert!(matches!(types[..], [
+        (TokenType::Punc(_), None),
+        (TokenType::Template, Some("a")),
+        (TokenType::Punc(_), None),
+        (TokenType::Name, Some("b")),
+        (TokenType::Punc(_), None),
+        (TokenType::Template, Some("c")),
+        (TokenType::Punc(_), None)
+    ]), "{:?}", types);
+    let sources: Vec<_> = tokens.iter().map(|t| t.source).collect();
+    assert_eq!(sources, ["`", "a", "${", "b", "}", "c", "`"]);
+}
+
+#[test]
+fn template_nesting() {
+    let chunks = |source| -> Vec<String> {
+        tokens(source).into_iter()
+            .filter(|t| t.token_type == TokenType::Template)
+            .map(|t| t.value.as_deref().unwrap().to_string())
+            .collect()
+    };
+    assert_eq!(chunks("`a${`b${c}d`}e`"), ["a", "b", "d", "e"]);
+    // braces inside a substitution don't close it
+    assert_eq!(chunks("`a${ {b: {}}.b }c`"), ["a", "c"]);
+    assert_eq!(chunks("`${a}${b}`"), ["", "", ""]);
+    assert_eq!(chunks("``"), [""]);
+    let source = "`${`${a}`}`";
+    let sources: Vec<_> = tokens(source).iter().map(|t| t.source).collect();
+    assert_eq!(sources, ["`", "", "${", "`", "", "${", "a", "}", "", "`", "}", "", "`"]);
+}
+
+#[test]
+fn template_cooked_and_raw() {
+    let tokens = tokens("`\\n\\u0041\r\nb`");
+    assert_eq!(tokens[1].value.as_deref(), Some("\nA\nb"));
+    assert_eq!(tokens[1].raw.as_deref(), Some("\\n\\u0041\nb"));
+    assert_eq!(error("`\\1`"), ParseErrorKind::OctalInTemplate);
+    assert_eq!(error("`a${b}c"), ParseErrorKind::UnterminatedTemplate);
+    assert_eq!(error("`a\\"), ParseErrorKind::UnterminatedTemplate);
+    assert_eq!(error_with("`a`", version(EcmaVersion::Ecma5)), ParseErrorKind::UnexpectedCharacter);
+}
+
+#[test]
+fn token_locations() {
+    let locations: Vec<_> = tokens("a\n  bc\r\n'\u{1F600}' d\u{2028}e").iter()
+        .map(|t| (t.loc.start.line, t.loc.start.column, t.loc.end.line, t.loc.end.column))
+        .collect();
+    // columns count UTF-16 code units and '\r\n' is one line break
+    assert_eq!(locations, [(1, 0, 1, 1), (2, 2, 2, 4), (3, 0, 3, 4), (3, 5, 3, 6), (4, 0, 4, 1)]);
+}
+
+#[test]
+fn errors_end_iteration() {
+    let mut tokenizer = Tokenizer::new("a @ b", Options::default());
+    assert!(matches!(tokenizer.next(), Some(Ok(_))));
+    let error = tokenizer.next().unwrap().unwrap_err();
+    assert_eq!((error.kind, error.pos), (ParseErrorKind::UnexpectedCharacter, 2));
+    assert!(tokenizer.next().is_none());
+}
+
+#[test]
+fn hashbang_and_html_comments() {
+    let values = |source, options| -> Vec<String> {
+        tokens_with(source, options).iter().map(|t| t.value.as_deref().unwrap_or("").to_string()).collect()
+    };
+    assert_eq!(values("#!/usr/bin/env node\na", Options::default()), ["a"]);
+    assert_eq!(values("a <!-- b\nc", Options::default()), ["a", "c"]);
+    assert_eq!(values("a\n  /* b */ --> c\nd", Options::default()), ["a", "d"]);
+    // '-->' after code on the same line is a decrement and a comparison
+    assert_eq!(values("a --> b", Options::default()), ["a", "--", ">", "b"]);
+    // modules don't have HTML-like comments
+    let module = Options { source_type: SourceType::Module, ..Options::default() };
+    assert_eq!(values("a <!-- b", module), ["a", "<", "!", "--", "b"]);
+
+    let mut tokenizer = Tokenizer::new("#!a\n<!-- b\n", Options { collect_comments: true, ..Options::default() });
+    for token in tokenizer.by_ref() {
+        token.unwrap();
+    }
+    let comments: Vec<_> = tokenizer.comments().iter().map(|c| (c.kind, c.text.to_string())).collect();
+    assert_eq!(comments, [(CommentKind::Hashbang, "a".to_string()), (CommentKind::Html, " b".to_string())]);
+}
+
+#[test]
+fn word_token_types() {
+    let types: Vec<_> = tokens("null true false typeof void delete a in b instanceof c class const").iter()
+        .map(|t| match t.token_type {
+            TokenType::Value(_) => "value",
+            TokenType::Operator(_) => "operator",
+            TokenType::Keyword(_) => "keyword",
+            TokenType::Name => "name",
+            _ => "other"
+        })
+        .collect();
+    assert_eq!(types, [
+        "value", "value", "value", "operator", "operator", "operator", "name",
+        "operator", "name", "operator", "name", "keyword", "keyword"
+    ]);
+}
+
+#[test]
+fn contextual_words() {
+    let tokens = tokens("let async of yield l\\u0065t x");
+    let flags: Vec<_> = tokens.iter().map(|t| (t.token_type == TokenType::Name, t.contextual)).collect();
+    // escaped contextual words are plain names
+    assert_eq!(flags, [(true, true), (true, true), (true, true), (true, true), (true, false), (true, false)]);
+}
+
+#[test]
+fn private_names() {
+    let tokens = tokens("this.#a #\\u0062");
+    let names: Vec<_> = tokens.iter().filter(|t| t.token_type == TokenType::PrivateName).map(|t| (t.value.as_deref(), t.source)).collect();
+    assert_eq!(names, [(Some("a"), "#a"), (Some("b"), "#\\u0062")]);
+    assert_eq!(error("# a"), ParseErrorKind::UnexpectedCharacter);
+}
+
+#[test]
+fn tolerant_mode_recovers() {
+    let source = "a @ b # 'c\nd";
+    let mut tokenizer = Tokenizer::new(source, Options { tolerant: true, ..Options::default() });
+    let tokens: Vec<_> = tokenizer.by_ref().map(|t| t.unwrap()).collect();
+    let sources: Vec<_> = tokens.iter().map(|t| (t.token_type == TokenType::Invalid, t.source)).collect();
+    assert_eq!(sources, [(false, "a"), (true, "@"), (false, "b"), (true, "#"), (true, "'c"), (false, "d")]);
+    let errors: Vec<_> = tokenizer.errors().iter().map(|e| (e.kind, e.pos)).collect();
+    assert_eq!(errors, [
+        (ParseErrorKind::UnexpectedCharacter, 2),
+        (ParseErrorKind::UnexpectedCharacter, 6),
+        (ParseErrorKind::UnterminatedStringConstant, 8)
+    ]);
+    // without tolerant mode, tokenizing stops at the first error
+    assert_eq!(Tokenizer::new(source, Options::default()).tokenize_all().unwrap_err().len(), 1);
+}
+
+#[test]
+fn tokens_borrow_the_source() {
+    let tokens = tokens("foo 'bar' `baz` 'q\\x75x'");
+    assert!(matches!(tokens[0].value, Some(Cow::Borrowed("foo"))));
+    assert!(matches!(tokens[1].value, Some(Cow::Borrowed("bar"))));
+    assert!(matches!(tokens[3].value, Some(Cow::Borrowed("baz"))));
+    // escapes have to be decoded into a new string
+    assert!(matches!(tokens[5].value, Some(Cow::Owned(_))));
+    assert_eq!(tokens[5].source, "'q\\x75x'");
+}
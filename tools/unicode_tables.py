@@ -0,0 +1,86 @@
+#!/usr/bin/env python3
+# Generates src/unicode.rs, the ID_Start and ID_Continue tables used to
+# tell identifier characters. The Unicode version is the one of the
+# Python running the script:
+#
+#     python3 tools/unicode_tables.py > src/unicode.rs
+
+import sys
+import unicodedata
+
+# Not in unicodedata, these lists are stable
+OTHER_ID_START = [0x1885, 0x1886, 0x2118, 0x212E, 0x309B, 0x309C]
+OTHER_ID_CONTINUE = [0x00B7, 0x0387, *range(0x1369, 0x1372), 0x19DA, 0x200C, 0x200D, 0x30FB, 0xFF65]
+PATTERN_SYNTAX = [
+    (0x0021, 0x002F), (0x003A, 0x0040), (0x005B, 0x005E), (0x0060, 0x0060), (0x007B, 0x007E),
+    (0x00A1, 0x00A7), (0x00A9, 0x00A9), (0x00AB, 0x00AC), (0x00AE, 0x00AE), (0x00B0, 0x00B1),
+    (0x00B6, 0x00B6), (0x00BB, 0x00BB), (0x00BF, 0x00BF), (0x00D7, 0x00D7), (0x00F7, 0x00F7),
+    (0x2010, 0x2027), (0x2030, 0x203E), (0x2041, 0x2053), (0x2055, 0x205E), (0x2190, 0x245F),
+    (0x2500, 0x2775), (0x2794, 0x2BFF), (0x2E00, 0x2E7F), (0x3001, 0x3003), (0x3008, 0x3020),
+    (0x3030, 0x3030), (0xFD3E, 0xFD3F), (0xFE45, 0xFE46),
+]
+PATTERN_WHITE_SPACE = [0x0009, 0x000A, 0x000B, 0x000C, 0x000D, 0x0020, 0x0085, 0x200E, 0x200F, 0x2028, 0x2029]
+
+START_CATEGORIES = {"Lu", "Ll", "Lt", "Lm", "Lo", "Nl"}
+CONTINUE_CATEGORIES = START_CATEGORIES | {"Mn", "Mc", "Nd", "Pc"}
+
+
+def is_pattern(code):
+    return code in PATTERN_WHITE_SPACE or any(first <= code <= last for first, last in PATTERN_SYNTAX)
+
+
+def ranges(predicate):
+    result = []
+    # ASCII is handled by the tokenizer
+    for code in range(0x80, sys.maxunicode + 1):
+        if not predicate(code):
+            continue
+        if result and result[-1][1] == code - 1:
+            result[-1][1] = code
+        else:
+            result.append([code, code])
+    return result
+
+
+def is_id_start(code):
+    category = unicodedata.category(chr(code))
+    return (category in START_CATEGORIES or code in OTHER_ID_START) and not is_pattern(code)
+
+
+def is_id_continue(code):
+    category = unicodedata.category(chr(code))
+    return (category in CONTINUE_CATEGORIES or code in OTHER_ID_START or code in OTHER_ID_CONTINUE) \
+        and not is_pattern(code)
+
+
+def table(name, comment, table_ranges):
+    lines = ["// " + comment, "pub static %s: &[(u32, u32)] = &[" % name]
+    items = ["(0x%04X, 0x%04X)" % (first, last) for first, last in table_ranges]
+    for i in range(0, len(items), 5):
+        lines.append("    " + ", ".join(items[i:i + 5]) + ",")
+    lines.append("];")
+    return "\n".join(lines)
+
+
+print("""// Non-ASCII identifier ranges, generated by tools/unicode_tables.py
+// from Unicode %s. Each table is a sorted list of inclusive
+// (first, last) code point ranges.
+
+use std::cmp::Ordering;
+""" % unicodedata.unidata_version)
+print(table("ID_START", "ID_Start", ranges(is_id_start)))
+print()
+print(table("ID_CONTINUE", "ID_Continue, which includes ZWNJ and ZWJ", ranges(is_id_continue)))
+print("""
+/// test if code point falls into one of the sorted ranges
+pub fn in_ranges(code: u32, ranges: &[(u32, u32)]) -> bool {
+    ranges.binary_search_by(|&(first, last)| {
+        if last < code {
+            Ordering::Less
+        } else if first > code {
+            Ordering::Greater
+        } else {
+            Ordering::Equal
+        }
+    }).is_ok()
+}""")